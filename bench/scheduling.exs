@@ -0,0 +1,56 @@
+# Measures how long individual NIF calls take, to decide which of them should
+# run on dirty schedulers.
+#
+# A NIF should return to the BEAM within about 1ms; anything that can take
+# longer than that blocks its scheduler and should be marked as a dirty NIF
+# instead. Calls that only record state on the context (`move_to/2`,
+# `line_to/2`, `set_color/2`, etc.) take a few microseconds regardless of the
+# canvas size and stay on the regular schedulers, while calls that rasterize
+# (`stroke/1`, `fill/1`, `paint/1`, `mask/2`, `show_text/2`) or write to disk
+# (`save_image/1`) grow with the size of the image and the complexity of the
+# path, and cross the 1ms threshold on large canvases.
+#
+# A regular call never waits for an image that another process is drawing
+# on: if the image is locked, the call is rescheduled onto a dirty CPU
+# scheduler and waits there instead.
+#
+# Run with
+#
+#     mix run bench/scheduling.exs
+
+alias Xairo.Pattern.LinearGradient
+alias Xairo.RGBA
+
+path = fn %{width: width, height: height} = image ->
+  image
+  |> Xairo.set_line_width(width / 10)
+  |> Xairo.arc({width / 2, height / 2}, width / 3, 0, 2 * :math.pi())
+  |> Xairo.rectangle({width / 4, height / 4}, width / 2, height / 2)
+end
+
+gradient =
+  LinearGradient.new({0, 0}, {1000, 1000})
+  |> LinearGradient.add_color_stop(0, RGBA.new(0, 0, 0, 0))
+  |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 0, 1))
+
+Benchee.run(
+  %{
+    "move_to" => fn image -> Xairo.move_to(image, {10, 10}) end,
+    "set_color" => fn image -> Xairo.set_color(image, 0.5, 0.5, 0.5) end,
+    "stroke" => fn image -> image |> path.() |> Xairo.stroke() end,
+    "fill" => fn image -> image |> path.() |> Xairo.fill() end,
+    "paint" => fn image -> Xairo.paint(image) end,
+    "mask" => fn image -> Xairo.mask(image, gradient) end,
+    "save_image" => fn image -> Xairo.save_image(image) end
+  },
+  inputs: %{
+    "100x100" => {100, 100},
+    "1000x1000" => {1000, 1000},
+    "4000x4000" => {4000, 4000}
+  },
+  before_each: fn {width, height} ->
+    Xairo.new_image("bench.png", width, height)
+    |> Xairo.set_color(0, 0, 0)
+  end,
+  after_scenario: fn _ -> File.rm("bench.png") end
+)
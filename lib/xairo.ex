@@ -19,6 +19,7 @@ defmodule Xairo do
 
     * `scale`: determines the scale of the image relative to the given width and height. See below for details on how scaling an image works.
     * `unit`: only read when creating SVG images, this determines the document unit the width and height dimensions will be measured in, defaulting to `:point`. See `Xairo.Image.Svg` for a list of allowed units. All other image types are understood to be defined in pixel size.
+    * `format`: only read when creating PNG images, this determines the pixel format of the image, defaulting to `:argb32`. See `Xairo.Image.Png` for a list of allowed formats.
 
   The following discussions of userspace, scale, and transformations apply to
   all types of images. I have used PNG in examples below,
@@ -212,16 +213,22 @@ defmodule Xairo do
   @typedoc """
   A 2-element tuple representing an error.
 
-  The first element is always `:error`, and the second is a string defining
-  the error context.
+  The first element is always `:error`, and the second is a 2-element tuple
+  of an atom identifying the kind of error and a term with any further
+  details (e.g. the filename that could not be written, or the index of a
+  missing color stop), or `nil` if there are none.
   """
-  @type error :: {:error, String.t()}
+  @type error :: {:error, {atom(), term()}}
 
   @typedoc """
   Shorthand for a valid image type
   """
   @type image ::
-          Xairo.Image.Png.t() | Xairo.Image.Svg.t() | Xairo.Image.Pdf.t() | Xairo.Image.Ps.t()
+          Xairo.Image.Png.t()
+          | Xairo.Image.Svg.t()
+          | Xairo.Image.Pdf.t()
+          | Xairo.Image.Ps.t()
+          | Xairo.Recording.t()
 
   @typedoc """
   Shorthand for the API return type.
@@ -244,11 +251,13 @@ defmodule Xairo do
     Point,
     Rectangle,
     RGBA,
+    Text,
     Text.Font,
+    Text.FontFace,
     Vector
   }
 
-  alias Pattern.{LinearGradient, Mesh, RadialGradient, Solid, Surface}
+  require Pattern
 
   @doc """
   Creates and returns a new `Xairo.Image` struct
@@ -278,6 +287,9 @@ defmodule Xairo do
     Saving will not create any non-extant parent directories in the filename path,
     and will return an error tuple instead.
 
+    Saving an SVG, PDF, or PostScript image finishes it, after which any
+    further operations on the image return `{:error, {:resource_finished, nil}}`.
+
   ## Examples
 
       iex> Xairo.save_image(image)
@@ -285,7 +297,7 @@ defmodule Xairo do
 
       iex> image = Xairo.new_image("non/extant/path/test.png", 200, 200)
       iex> Xairo.save_image(image)
-      {:error, :file_creation_error}
+      {:error, {:file_create, "non/extant/path/test.png"}}
 
   """
   @spec save_image(image()) :: image_or_error()
@@ -293,6 +305,145 @@ defmodule Xairo do
     Image.save(image)
   end
 
+  @doc """
+  Returns the status of the image's drawing context as an atom.
+
+  This is `:success` for a valid context. Once an operation puts the context
+  into an error state (for example, scaling by 0 produces `:invalid_matrix`),
+  every subsequent drawing operation on it is ignored, and drawing and
+  transformation functions return `{:error, {:invalid_status, status}}`.
+
+  ## Examples
+
+      iex> Xairo.status(image)
+      :success
+
+      iex> image |> Xairo.scale(0, 0)
+      {:error, {:invalid_status, :invalid_matrix}}
+
+  """
+  @spec status(image()) :: atom() | error()
+  def status(%{resource: _} = image) do
+    with {:ok, status} <- Native.status(image.resource), do: status
+  end
+
+  @doc """
+  Returns a `Xairo.ImageInfo` struct describing the image's native surface.
+
+  This reads the backend type, pixel dimensions, stride, format, device scale
+  and offset, and finished state from the surface itself, and can be called
+  on an image that has already been finished.
+
+  ## Example
+
+      iex> Xairo.new_image("test.png", 100, 100, scale: 2.0)
+      ...> |> Xairo.image_info()
+      %Xairo.ImageInfo{
+        backend: :image,
+        width: 200,
+        height: 200,
+        stride: 800,
+        format: :argb32,
+        device_scale: {1.0, 1.0},
+        device_offset: {0.0, 0.0},
+        finished: false
+      }
+
+  """
+  @spec image_info(image() | Xairo.Mask.t()) :: Xairo.ImageInfo.t() | error()
+  def image_info(%{resource: _} = image) do
+    Xairo.ImageInfo.new(image)
+  end
+
+  @doc """
+  Returns the pixel format of a PNG image or `Xairo.Mask`.
+
+  See `Xairo.Image.Png` for the list of formats. SVG, PDF, and PostScript
+  images have no pixel format, and return
+  `{:error, {:file_type_mismatch, nil}}`.
+
+  ## Example
+
+      iex> Xairo.new_image("test.png", 100, 100, format: :rgb24)
+      ...> |> Xairo.image_format()
+      :rgb24
+
+  """
+  @spec image_format(image() | Xairo.Mask.t()) :: atom() | error()
+  def image_format(%{resource: _} = image) do
+    with {:ok, format} <- Native.image_format(image.resource), do: format
+  end
+
+  @doc """
+  Returns the raw pixel data of a PNG image or `Xairo.Mask` as a map.
+
+  The map contains the pixel `data` as a binary, along with the `width` and
+  `height` of the surface in pixels (with any scale applied), the `stride`
+  (the number of bytes in each row of pixels, which can be larger than the
+  width of a row), and the pixel `format`.
+
+  In the `:argb32` format each pixel is stored as a native-endian 32-bit
+  integer holding alpha, red, green, and blue in that order, with the color
+  values premultiplied by alpha. See `Xairo.Image.Png` for the layout of the
+  other formats.
+
+  Returns `{:error, {:file_type_mismatch, nil}}` for SVG, PDF, and PostScript
+  images, which have no pixel data.
+
+  ## Example
+
+      iex> Xairo.image_data(image)
+      %{data: <<0, 0, 0, 0, ...>>, width: 100, height: 100, stride: 400, format: :argb32}
+
+  """
+  @spec image_data(image() | Xairo.Mask.t()) :: map() | error()
+  def image_data(%{resource: _} = image) do
+    with {:ok, {data, width, height, stride, format}} <- Native.image_data(image.resource) do
+      %{data: data, width: width, height: height, stride: stride, format: format}
+    end
+  end
+
+  @doc """
+  Returns the color of the pixel at `x`, `y` as a `Xairo.RGBA` struct.
+
+  The coordinates are given in pixels on the underlying surface, so any scale
+  applied to the image is not taken into account. The color values are
+  returned un-premultiplied, so a half-transparent red pixel is returned as
+  `#RGBA<1.0, 0.0, 0.0, 0.5>`. Pixels of formats without an alpha channel are
+  returned as opaque, and pixels of `:a8` and `:a1` images as black with the
+  stored alpha.
+
+  ## Examples
+
+      iex> Xairo.get_pixel(image, 10, 10)
+      #RGBA<1.0, 0.0, 0.0, 1.0>
+
+      iex> Xairo.get_pixel(image, 1000, 10)
+      {:error, {:pixel_out_of_bounds, {1000, 10}}}
+
+  """
+  @spec get_pixel(image() | Xairo.Mask.t(), integer(), integer()) :: RGBA.t() | error()
+  def get_pixel(%{resource: _} = image, x, y) do
+    with {:ok, rgba} <- Native.get_pixel(image.resource, x, y), do: rgba
+  end
+
+  @doc """
+  Replaces the pixel data of a PNG image or `Xairo.Mask` with `data`.
+
+  `data` must be in the layout returned by `image_data/1`: exactly
+  `stride * height` bytes in the image's pixel format. A binary of the wrong
+  size returns `{:error, {:pixel_data, {size, expected_size}}}`.
+
+  ## Example
+
+      iex> %{data: data} = Xairo.image_data(image)
+      iex> Xairo.set_image_data(image, invert(data))
+      #Image<100x100@2.0 #Reference<0.123.456.789>>
+
+  """
+  @spec set_image_data(image() | Xairo.Mask.t(), binary()) :: image_or_error()
+  native_fn(:set_image_data, [data])
+
   @doc """
   Sets the image's current point to `point`.
 
@@ -321,10 +472,9 @@ defmodule Xairo do
   """
   @spec move_to(image(), Xairo.point()) :: image_or_error()
   def move_to(%{resource: _} = image, point) do
-    with point <- Point.from(point) do
-      Xairo.Native.move_to(image.resource, point)
-      image
-    end
+    with point <- Point.from(point),
+         {:ok, _} <- Xairo.Native.move_to(image.resource, point),
+         do: image
   end
 
   @doc """
@@ -507,10 +657,9 @@ defmodule Xairo do
   """
   @spec rel_move_to(image(), Vector.t()) :: image_or_error()
   def rel_move_to(%{resource: _} = image, vector) do
-    with vector <- Vector.from(vector) do
-      Xairo.Native.rel_move_to(image.resource, vector)
-      image
-    end
+    with vector <- Vector.from(vector),
+         {:ok, _} <- Xairo.Native.rel_move_to(image.resource, vector),
+         do: image
   end
 
   @doc """
@@ -529,10 +678,9 @@ defmodule Xairo do
   """
   @spec rel_line_to(image(), Vector.t()) :: image_or_error()
   def rel_line_to(%{resource: _} = image, vector) do
-    with vector <- Vector.from(vector) do
-      Xairo.Native.rel_line_to(image.resource, vector)
-      image
-    end
+    with vector <- Vector.from(vector),
+         {:ok, _} <- Xairo.Native.rel_line_to(image.resource, vector),
+         do: image
   end
 
   @doc """
@@ -718,35 +866,17 @@ defmodule Xairo do
   - `Xairo.Pattern.LinearGradient`
   - `Xairo.Pattern.RadialGradient`
   - `Xairo.Pattern.Mesh`
+  - `Xairo.Pattern.Solid`
+  - `Xairo.Pattern.Surface`
+  - `Xairo.Pattern.Raster`
 
   See the documentation for each module to understand how to construct
   it so that it can be set as the source with this function. All desired
   colors stops must be set before the source is set.
   """
   @spec set_source(image(), Pattern.pattern()) :: image_or_error()
-  def set_source(%{resource: _} = image, %LinearGradient{} = gradient) do
-    Native.set_linear_gradient_source(image.resource, gradient.pattern)
-    image
-  end
-
-  def set_source(%{resource: _} = image, %RadialGradient{} = gradient) do
-    Native.set_radial_gradient_source(image.resource, gradient.pattern)
-    image
-  end
-
-  def set_source(%{resource: _} = image, %Mesh{} = mesh) do
-    Native.set_mesh_source(image.resource, mesh.pattern)
-    image
-  end
-
-  def set_source(%{resource: _} = image, %Solid{} = pattern) do
-    Native.set_solid_pattern_source(image.resource, pattern.pattern)
-    image
-  end
-
-  def set_source(%{resource: _} = image, %Surface{} = pattern) do
-    Native.set_surface_pattern_source(image.resource, pattern.resource)
-    image
+  def set_source(%{resource: _} = image, pattern) when Pattern.is_pattern(pattern) do
+    with {:ok, _} <- Native.set_source(image.resource, pattern.pattern), do: image
   end
 
   @doc """
@@ -783,8 +913,17 @@ defmodule Xairo do
   to `show_text/2`.
 
   See `Xairo.Text.Font` for a discussion of the font struct.
+
+  A `t:Xairo.Text.FontFace.t/0` loaded from a font file can also be given, and
+  must be set on the image before glyphs shaped with it are rendered by
+  `show_glyphs/2`.
   """
-  @spec set_font_face(image(), Font.t()) :: image_or_error()
+  @spec set_font_face(image(), Font.t() | FontFace.t()) :: image_or_error()
+  def set_font_face(%{resource: _} = image, %FontFace{} = font_face) do
+    with {:ok, _} <- Native.set_loaded_font_face(image.resource, font_face.resource),
+         do: image
+  end
+
   native_fn(:set_font_face, [font])
 
   @doc """
@@ -818,6 +957,18 @@ defmodule Xairo do
   @spec set_font_matrix(image(), Matrix.t()) :: image_or_error()
   native_fn(:set_font_matrix, [matrix])
 
+  @doc """
+  Sets the antialiasing, hinting, and subpixel options used to render text on
+  the image.
+
+  Takes as an argument a `t:Xairo.Text.FontOptions.t/0`. Fixing these values
+  ensures text renders identically regardless of the system font configuration.
+
+  See `Xairo.Text.FontOptions` for a description of the struct and its fields.
+  """
+  @spec set_font_options(image(), Text.FontOptions.t()) :: image_or_error()
+  native_fn(:set_font_options, [options])
+
   @doc """
   Scales the image by the given amounts along the x and y axes.
 
@@ -892,10 +1043,9 @@ defmodule Xairo do
   Returns a `Xairo.Matrix` representing the image's current transformation
   matrix.
   """
-  @spec get_matrix(image()) :: Matrix.t()
+  @spec get_matrix(image()) :: Matrix.t() | error()
   def get_matrix(%{resource: _} = image) do
-    %Matrix{} = matrix = Xairo.Native.get_matrix(image.resource)
-    matrix
+    with {:ok, %Matrix{} = matrix} <- Xairo.Native.get_matrix(image.resource), do: matrix
   end
 
   @doc """
@@ -907,6 +1057,81 @@ defmodule Xairo do
   @spec set_document_unit(Image.Svg.t(), Image.Svg.svg_unit()) :: Image.Svg.t()
   native_fn(:set_document_unit, [unit])
 
+  @doc """
+  Sets the device scale of the image's surface.
+
+  Unlike the `scale` option to `new_image/4`, which scales the image's
+  transformation matrix, the device scale is applied by the surface between
+  userspace and device space, so it is not reflected in `get_matrix/1` and is
+  not reset by `identity_matrix/1` or `set_matrix/2`.
+
+  The device scale does not change the size of the surface, so a PNG image
+  drawn at a device scale of 2 should be created with twice the pixel width
+  and height.
+
+  ## Example
+
+      iex> Xairo.new_image("hidpi.png", 200, 200)
+      ...> |> Xairo.set_device_scale(2, 2)
+      ...> |> Xairo.rectangle({10, 10}, 80, 80)
+      ...> |> Xairo.fill()
+
+  """
+  @spec set_device_scale(image() | Xairo.Mask.t(), number(), number()) :: image_or_error()
+  native_fn(:set_device_scale, [{sx, Float}, {sy, Float}])
+
+  @doc """
+  Returns the device scale of the image's surface as an `{x, y}` tuple.
+  """
+  @spec device_scale(image() | Xairo.Mask.t()) :: {float(), float()} | error()
+  def device_scale(%{resource: _} = image) do
+    with {:ok, scale} <- Native.device_scale(image.resource), do: scale
+  end
+
+  @doc """
+  Sets the device offset of the image's surface.
+
+  The offset is added to device coordinates after the device scale is
+  applied, and like the device scale it is not reflected in the image's
+  transformation matrix.
+  """
+  @spec set_device_offset(image() | Xairo.Mask.t(), number(), number()) :: image_or_error()
+  native_fn(:set_device_offset, [{x, Float}, {y, Float}])
+
+  @doc """
+  Returns the device offset of the image's surface as an `{x, y}` tuple.
+  """
+  @spec device_offset(image() | Xairo.Mask.t()) :: {float(), float()} | error()
+  def device_offset(%{resource: _} = image) do
+    with {:ok, offset} <- Native.device_offset(image.resource), do: offset
+  end
+
+  @doc """
+  Sets the resolution, in pixels per inch, at which an SVG, PDF, or PostScript
+  image rasterizes the parts of a drawing that it cannot represent as vector
+  operations.
+
+  Cairo defaults to 300 pixels per inch. PNG images have no fallback
+  resolution, and return `{:error, {:file_type_mismatch, nil}}`.
+
+  ## Example
+
+      iex> Xairo.new_image("test.pdf", 100, 100)
+      ...> |> Xairo.set_fallback_resolution(600, 600)
+
+  """
+  @spec set_fallback_resolution(image(), number(), number()) :: image_or_error()
+  native_fn(:set_fallback_resolution, [{x_ppi, Float}, {y_ppi, Float}])
+
+  @doc """
+  Returns the fallback resolution of the image's surface as an `{x, y}` tuple
+  of pixels per inch.
+  """
+  @spec fallback_resolution(image()) :: {float(), float()} | error()
+  def fallback_resolution(%{resource: _} = image) do
+    with {:ok, resolution} <- Native.fallback_resolution(image.resource), do: resolution
+  end
+
   @doc """
   Copies the current path from the image and returns it as a `Xairo.Path` struct.
   """
@@ -936,8 +1161,7 @@ defmodule Xairo do
   """
   @spec append_path(image(), Xairo.Path.t()) :: image_or_error()
   def append_path(%{resource: _} = image, %Xairo.Path{resource: path}) do
-    Xairo.Native.append_path(image.resource, path)
-    image
+    with {:ok, _} <- Xairo.Native.append_path(image.resource, path), do: image
   end
 
   @doc """
@@ -946,9 +1170,9 @@ defmodule Xairo do
   Tolerance determines how precisely a curved line is translated into straight
   line segments when calling `copy_path_flat/1`.
   """
-  @spec get_tolerance(image()) :: float
+  @spec get_tolerance(image()) :: float | error()
   def get_tolerance(%{resource: _} = image) do
-    Xairo.Native.get_tolerance(image.resource)
+    with {:ok, tolerance} <- Xairo.Native.get_tolerance(image.resource), do: tolerance
   end
 
   @doc """
@@ -967,23 +1191,8 @@ defmodule Xairo do
     applied to the final image.
   """
   @spec mask(image(), Xairo.Pattern.pattern()) :: image_or_error()
-  def mask(%{resource: _} = image, %RadialGradient{} = gradient) do
-    with {:ok, _} <- Xairo.Native.set_radial_gradient_mask(image.resource, gradient.pattern),
-         do: image
-  end
-
-  def mask(%{resource: _} = image, %LinearGradient{} = gradient) do
-    with {:ok, _} <- Xairo.Native.set_linear_gradient_mask(image.resource, gradient.pattern),
-         do: image
-  end
-
-  def mask(%{resource: _} = image, %Mesh{} = mesh) do
-    with {:ok, _} <- Xairo.Native.set_mesh_mask(image.resource, mesh.pattern), do: image
-  end
-
-  def mask(%{resource: _} = image, %Solid{} = pattern) do
-    with {:ok, _} <- Xairo.Native.set_solid_pattern_mask(image.resource, pattern.pattern),
-         do: image
+  def mask(%{resource: _} = image, pattern) when Pattern.is_pattern(pattern) do
+    with {:ok, _} <- Native.mask(image.resource, pattern.pattern), do: image
   end
 
   @doc """
@@ -1000,16 +1209,94 @@ defmodule Xairo do
     with {:ok, _} <- Native.mask_surface(image.resource, mask.resource, point), do: image
   end
 
+  @doc """
+    Returns a sub-image that draws into the given rectangle of `image`.
+
+    The sub-image is the same kind of struct as `image`, with its origin at
+    the corner of the rectangle and its width and height set to those of the
+    rectangle, and anything drawn to it is clipped to the rectangle. This
+    makes it easy to lay out independently drawn panels, such as the charts
+    of a dashboard, on a single image. The scale of `image` is applied to
+    the sub-image as well.
+
+    The sub-image cannot be saved itself. Its contents are saved along with
+    `image`.
+
+  ## Example
+
+      iex> image = Xairo.new_image("dashboard.png", 200, 100)
+      iex> left = Xairo.sub_image(image, Rectangle.new({0, 0}, 100, 100))
+      iex> right = Xairo.sub_image(image, Rectangle.new({100, 0}, 100, 100))
+      iex> draw_chart(left, data)
+      iex> draw_chart(right, other_data)
+      iex> Xairo.save_image(image)
+
+  """
+  @spec sub_image(image(), Rectangle.t()) :: image_or_error()
+  def sub_image(%{resource: resource} = image, %Rectangle{corner: corner} = rectangle) do
+    scale = Map.get(image, :scale, 1.0)
+
+    bounds =
+      Rectangle.new(
+        {corner.x * scale, corner.y * scale},
+        rectangle.width * scale,
+        rectangle.height * scale
+      )
+
+    with {:ok, sub_image} <- Native.create_for_rectangle(resource, bounds) do
+      Native.scale(sub_image, scale, scale)
+      struct(image, resource: sub_image, width: rectangle.width, height: rectangle.height)
+    end
+  end
+
+  @doc """
+    Replays the drawing operations stored in a `Xairo.Recording` onto the
+    image, with the recording's origin placed at `origin`.
+
+    The recording can be scaled by passing a `scale` option, either as a
+    single number or as an `{sx, sy}` tuple. The image's own transformation
+    matrix, source, and path are left unchanged.
+
+  ## Example
+
+      iex> Xairo.new_image("figure.pdf", 200, 200)
+      ...> |> Xairo.replay(recording, {0, 0}, scale: 2)
+      ...> |> Xairo.save_image()
+
+  """
+  @spec replay(image(), Xairo.Recording.t(), Xairo.point(), Keyword.t()) :: image_or_error()
+  def replay(
+        %{resource: _} = image,
+        %Xairo.Recording{} = recording,
+        origin \\ {0, 0},
+        opts \\ []
+      ) do
+    {sx, sy} =
+      case Keyword.get(opts, :scale, 1) do
+        {sx, sy} -> {sx, sy}
+        scale -> {scale, scale}
+      end
+
+    with {:ok, _} <-
+           Native.replay(
+             image.resource,
+             recording.resource,
+             Point.from(origin),
+             sx * 1.0,
+             sy * 1.0
+           ),
+         do: image
+  end
+
   @doc """
     Begins a new path on the image, clearing the existing path.
 
     Any current path components that have not been rendered are removed
     from the path.
   """
-  @spec new_path(image()) :: image()
+  @spec new_path(image()) :: image_or_error()
   def new_path(%{resource: _} = image) do
-    Native.new_path(image.resource)
-    image
+    with {:ok, _} <- Native.new_path(image.resource), do: image
   end
 
   @doc """
@@ -1024,10 +1311,49 @@ defmodule Xairo do
     the beginning of the arc in order to avoid a line connecting it to the previous
     current point.
   """
-  @spec new_sub_path(image()) :: image()
+  @spec new_sub_path(image()) :: image_or_error()
   def new_sub_path(%{resource: _} = image) do
-    Native.new_sub_path(image.resource)
-    image
+    with {:ok, _} <- Native.new_sub_path(image.resource), do: image
+  end
+
+  @doc """
+    Adds a series of straight lines through the given points to the current
+    path, starting with a move to the first point.
+
+    `points` can be a list of points or coordinate tuples, or, for large data
+    sets, a binary of packed little-endian 64-bit float x/y pairs, which avoids
+    the cost of decoding each point individually.
+
+    ## Example
+
+    ```
+    points = for x <- 0..100, into: <<>>, do: <<x::little-float-64, :math.sin(x)::little-float-64>>
+    Xairo.polyline(image, points)
+    ```
+  """
+  @spec polyline(image(), binary() | [Xairo.point()]) :: image_or_error()
+  def polyline(%{resource: _} = image, points) do
+    with {:ok, _} <- Native.polyline(image.resource, pack_points(points)), do: image
+  end
+
+  @doc """
+    Adds a closed polygon through the given points to the current path.
+
+    Takes the same arguments as `polyline/2`, and closes the path back to the
+    first point.
+  """
+  @spec polygon(image(), binary() | [Xairo.point()]) :: image_or_error()
+  def polygon(%{resource: _} = image, points) do
+    with {:ok, _} <- Native.polygon(image.resource, pack_points(points)), do: image
+  end
+
+  defp pack_points(points) when is_binary(points), do: points
+
+  defp pack_points(points) when is_list(points) do
+    for point <- points, into: <<>> do
+      %Point{x: x, y: y} = Point.from(point)
+      <<x::little-float-64, y::little-float-64>>
+    end
   end
 
   @doc """
@@ -1047,6 +1373,168 @@ defmodule Xairo do
     Calling `fill/1` subequently to this function is equivalent to
     calling `show_text/2` with the same text.
   """
-  @spec text_path(image(), String.t()) :: image()
+  @spec text_path(image(), String.t()) :: image_or_error()
   native_fn(:text_path, [text])
+
+  @doc """
+    Adds closed paths to the current path representing the given text, shaped
+    with `font_face`, laid out along `path`.
+
+    The text is shaped as with `Xairo.Text.shape/4`, so kerning, ligatures,
+    combining marks and right-to-left text are laid out as they would be on a
+    straight line. Each cluster of glyphs (e.g. a ligature, or a letter and its
+    accents) is then placed on the path so that its baseline follows the path,
+    rotated to match the path's direction at that point. Clusters that would
+    fall before the start or past the end of the path are skipped. As with
+    `text_path/2`, nothing is rendered until `fill/1` or `stroke/1` is called.
+
+    This sets `font_face` as the image's font face, as `show_shaped_text/4`
+    does.
+
+    The following options can be given, along with any of the options accepted
+    by `Xairo.Text.shape/4`
+
+    - `offset`: the distance along the path, in userspace, at which to start the
+      text. Defaults to 0
+    - `align`: one of `:start`, `:center`, or `:end`, determining where on the
+      path the text is placed before the offset is applied. Defaults to `:start`
+
+    ## Example
+
+    ```
+    face = Xairo.Text.FontFace.load("fonts/DejaVuSans.ttf")
+    image = Xairo.arc(image, {50, 50}, 40, :math.pi(), 2 * :math.pi())
+    path = Xairo.copy_path(image)
+
+    image
+    |> Xairo.new_path()
+    |> Xairo.text_path_along(path, face, "Hello, world", align: :center)
+    |> Xairo.fill()
+    ```
+  """
+  @spec text_path_along(image(), Xairo.Path.t(), FontFace.t(), String.t(), keyword()) ::
+          image_or_error()
+  def text_path_along(
+        %{resource: _} = image,
+        %Xairo.Path{resource: path},
+        %FontFace{} = font_face,
+        text,
+        opts \\ []
+      ) do
+    offset = Keyword.get(opts, :offset, 0) * 1.0
+    align = Keyword.get(opts, :align, :start)
+
+    with %{resource: _} = image <- set_font_face(image, font_face),
+         %Text.ShapingOptions{} = options <- Text.ShapingOptions.new(opts),
+         {:ok, _} <-
+           Native.text_path_along(
+             image.resource,
+             path,
+             font_face.resource,
+             text,
+             offset,
+             align,
+             options
+           ),
+         do: image
+  end
+
+  @doc """
+    Renders the given list of `t:Xairo.Text.Glyph.t/0` structs onto the image
+    using the current font face.
+
+    Glyph indices are specific to a font face, so the face the glyphs were
+    shaped with (see `Xairo.Text.shape/4`) should be set with `set_font_face/2`
+    before calling this function.
+  """
+  @spec show_glyphs(image(), [Text.Glyph.t()]) :: image_or_error()
+  def show_glyphs(%{resource: _} = image, glyphs) when is_list(glyphs) do
+    with {:ok, _} <- Native.show_glyphs(image.resource, glyphs), do: image
+  end
+
+  @doc """
+    Adds closed paths to the current path representing the given glyphs.
+
+    This is the glyph equivalent of `text_path/2`.
+  """
+  @spec glyph_path(image(), [Text.Glyph.t()]) :: image_or_error()
+  def glyph_path(%{resource: _} = image, glyphs) when is_list(glyphs) do
+    with {:ok, _} <- Native.glyph_path(image.resource, glyphs), do: image
+  end
+
+  @doc """
+    Shapes `text` with `font_face` and renders it at the image's current point.
+
+    This sets `font_face` as the image's font face, and is equivalent to
+    calling `set_font_face/2`, `Xairo.Text.shape/4`, and `show_glyphs/2` in turn.
+    See `Xairo.Text.shape/4` for the accepted options.
+  """
+  @spec show_shaped_text(image(), FontFace.t(), String.t(), keyword()) :: image_or_error()
+  def show_shaped_text(%{resource: _} = image, %FontFace{} = font_face, text, opts \\ []) do
+    with %{resource: _} = image <- set_font_face(image, font_face),
+         glyphs when is_list(glyphs) <- Text.shape(image, font_face, text, opts) do
+      show_glyphs(image, glyphs)
+    end
+  end
+
+  @doc """
+    Runs a list of drawing commands against the image in a single call to the
+    native layer.
+
+    Each call to a drawing function crosses into the native layer, which adds up
+    when drawing many thousands of segments. Batching the commands avoids that
+    overhead.
+
+    Commands without arguments are given as atoms, and commands with arguments
+    as tuples tagged with the name of the matching `Xairo` function:
+
+    - `{:move_to, point}`, `{:line_to, point}`
+    - `{:rel_move_to, vector}`, `{:rel_line_to, vector}`
+    - `{:curve_to, curve}`, `{:rel_curve_to, curve}`
+    - `{:arc, arc}`, `{:arc_negative, arc}`
+    - `{:rectangle, rectangle}`
+    - `:close_path`, `:new_path`, `:new_sub_path`
+    - `{:set_color, rgba}`, `{:set_line_width, width}`
+    - `:stroke`, `:fill`, `:paint`
+    - `:save`, `:restore`
+    - `{:translate, dx, dy}`, `{:scale, sx, sy}`, `{:rotate, rad}`
+    - `{:transform, matrix}`, `{:set_matrix, matrix}`, `:identity_matrix`
+
+    Points and vectors can be given as coordinate tuples.
+
+    Commands are run in order, and processing stops at the first command that
+    fails, returning `{:error, {:draw_command, {index, error}}}` with the index
+    of that command.
+
+    ## Example
+
+    ```
+    Xairo.draw_commands(image, [
+      {:set_color, RGBA.new(0, 0, 0)},
+      {:move_to, {10, 10}},
+      {:line_to, {90, 90}},
+      :stroke
+    ])
+    ```
+  """
+  @spec draw_commands(image(), [atom() | tuple()]) :: image_or_error()
+  def draw_commands(%{resource: _} = image, commands) when is_list(commands) do
+    commands = Enum.map(commands, &normalize_command/1)
+
+    with {:ok, _} <- Native.draw_commands(image.resource, commands), do: image
+  end
+
+  defp normalize_command({command, point}) when command in [:move_to, :line_to],
+    do: {command, Point.from(point)}
+
+  defp normalize_command({command, vector}) when command in [:rel_move_to, :rel_line_to],
+    do: {command, Vector.from(vector)}
+
+  defp normalize_command({command, value}) when command in [:set_line_width, :rotate],
+    do: {command, value * 1.0}
+
+  defp normalize_command({command, x, y}) when command in [:translate, :scale],
+    do: {command, x * 1.0, y * 1.0}
+
+  defp normalize_command(command), do: command
 end
@@ -26,6 +26,26 @@ defmodule Xairo.Image.Png do
 
   will produce a 300x300 pixel image with a line from (30, 30) to (270, 270).
 
+  ## Formats
+
+  The pixel format of the image can be set with the `format` option, and
+  defaults to `:argb32`.
+
+  * `:argb32`: 32 bits per pixel, with alpha, red, green, and blue channels,
+    stored as a native-endian integer with premultiplied color values.
+  * `:rgb24`: 32 bits per pixel, with red, green, and blue channels in the
+    lower 24 bits and no alpha channel.
+  * `:a8`: 8 bits per pixel holding only an alpha value.
+  * `:a1`: 1 bit per pixel holding only an alpha value, packed into
+    native-endian 32-bit words.
+  * `:rgb16565`: 16 bits per pixel, with 5 bits of red, 6 of green, and 5 of
+    blue.
+  * `:rgb30`: 32 bits per pixel, with 10 bits each of red, green, and blue in
+    the lower 30 bits.
+
+  Images in every format are saved as PNG files, with cairo converting
+  formats that PNG cannot store directly to the closest format it can.
+
   """
 
   defstruct [
@@ -69,7 +89,8 @@ defmodule Xairo.Image.Png do
     scale = Keyword.get(options, :scale, 1.0) * 1.0
     scaled_width = round(scale * width)
     scaled_height = round(scale * height)
-    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height)
+    format = Keyword.get(options, :format, :argb32)
+    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height, format)
     Xairo.Native.scale(resource, scale, scale)
 
     %__MODULE__{
@@ -0,0 +1,48 @@
+defmodule Xairo.ImageInfo do
+  @moduledoc """
+    Describes the native surface behind an image, as reported by cairo.
+
+    * `backend`: the surface type, one of `:image` (for PNG images and masks),
+      `:svg`, `:pdf`, `:ps`, or `:recording` (for `Xairo.Recording`)
+    * `width` and `height`: the size of the surface in pixels, with any scale
+      applied
+    * `stride`: the number of bytes in each row of pixels
+    * `format`: the pixel format of the surface (see `Xairo.Image.Png`)
+    * `device_scale`: the `{x, y}` device scale of the surface
+    * `device_offset`: the `{x, y}` device offset of the surface
+    * `finished`: whether the surface has been finished, after which it can
+      no longer be drawn to
+
+    `width`, `height`, `stride`, and `format` are only set for image
+    surfaces, and are `nil` for SVG, PDF, and PostScript images.
+
+    Unlike the `width` and `height` stored on the image structs, which are
+    given in userspace units, these values are read from the surface itself.
+
+  """
+  defstruct [
+    :backend,
+    :width,
+    :height,
+    :stride,
+    :format,
+    :device_scale,
+    :device_offset,
+    :finished
+  ]
+
+  @type t :: %__MODULE__{
+          backend: atom(),
+          width: integer() | nil,
+          height: integer() | nil,
+          stride: integer() | nil,
+          format: atom() | nil,
+          device_scale: {number(), number()},
+          device_offset: {number(), number()},
+          finished: boolean()
+        }
+
+  def new(%{resource: _} = image) do
+    with {:ok, %__MODULE__{} = info} <- Xairo.Native.image_info(image.resource), do: info
+  end
+end
@@ -16,6 +16,9 @@ defmodule Xairo.Mask do
     transparent, with the alpha values from the mask applied to the
     final image.
 
+    Since only the alpha values of a mask are used, a mask can be created
+    with `format: :a8` to store only an alpha channel.
+
     The surface mask is applied via `Xairo.mask_surface/3` which takes as
     its arguments the base image, the mask struct, and a coordinate pair
     defining where the origin of the mask should be set on the base image.
@@ -59,7 +62,8 @@ defmodule Xairo.Mask do
     scale = Keyword.get(options, :scale, 1.0) * 1.0
     scaled_width = round(scale * width)
     scaled_height = round(scale * height)
-    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height)
+    format = Keyword.get(options, :format, :argb32)
+    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height, format)
     Xairo.Native.scale(resource, scale, scale)
 
     %__MODULE__{
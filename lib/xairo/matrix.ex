@@ -73,7 +73,7 @@ defmodule Xairo.Matrix do
   """
   @spec translate(__MODULE__.t(), number, number) :: __MODULE__.t()
   def translate(%__MODULE__{} = matrix, xt, yt) do
-    Xairo.Native.matrix_translate(matrix, xt * 1.0, yt * 1.0)
+    with {:ok, matrix} <- Xairo.Native.matrix_translate(matrix, xt * 1.0, yt * 1.0), do: matrix
   end
 
   @doc """
@@ -81,7 +81,7 @@ defmodule Xairo.Matrix do
   """
   @spec scale(__MODULE__.t(), number, number) :: __MODULE__.t()
   def scale(%__MODULE__{} = matrix, xx, yy) do
-    Xairo.Native.matrix_scale(matrix, xx * 1.0, yy * 1.0)
+    with {:ok, matrix} <- Xairo.Native.matrix_scale(matrix, xx * 1.0, yy * 1.0), do: matrix
   end
 
   @doc """
@@ -89,7 +89,7 @@ defmodule Xairo.Matrix do
   """
   @spec rotate(__MODULE__.t(), number) :: __MODULE__.t()
   def rotate(%__MODULE__{} = matrix, radians) do
-    Xairo.Native.matrix_rotate(matrix, radians * 1.0)
+    with {:ok, matrix} <- Xairo.Native.matrix_rotate(matrix, radians * 1.0), do: matrix
   end
 
   @doc """
@@ -125,7 +125,7 @@ defmodule Xairo.Matrix do
   """
   @spec multiply(__MODULE__.t(), __MODULE__.t()) :: __MODULE__.t()
   def multiply(%__MODULE__{} = matrix1, %__MODULE__{} = matrix2) do
-    Xairo.Native.matrix_multiply(matrix1, matrix2)
+    with {:ok, matrix} <- Xairo.Native.matrix_multiply(matrix1, matrix2), do: matrix
   end
 
   alias Xairo.{Point, Vector}
@@ -135,7 +135,7 @@ defmodule Xairo.Matrix do
   """
   @spec transform_point(__MODULE__.t(), Point.t()) :: Point.t()
   def transform_point(%__MODULE__{} = matrix, %Point{} = point) do
-    Xairo.Native.matrix_transform_point(matrix, point)
+    with {:ok, point} <- Xairo.Native.matrix_transform_point(matrix, point), do: point
   end
 
   @doc """
@@ -143,7 +143,7 @@ defmodule Xairo.Matrix do
   """
   @spec transform_point(__MODULE__.t(), Vector.t()) :: Vector.t()
   def transform_distance(%__MODULE__{} = matrix, %Vector{} = vector) do
-    Xairo.Native.matrix_transform_distance(matrix, vector)
+    with {:ok, vector} <- Xairo.Native.matrix_transform_distance(matrix, vector), do: vector
   end
 
   defimpl Inspect do
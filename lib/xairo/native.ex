@@ -3,21 +3,44 @@ defmodule Xairo.Native do
 
   use Rustler, otp_app: :xairo, crate: "xairo"
 
-  def new_png_image(_w, _h), do: error()
+  def new_png_image(_w, _h, _f), do: error()
   def new_pdf_image(_w, _h, _f), do: error()
   def new_ps_image(_w, _h, _f), do: error()
   def new_svg_image(_f, _w, _h), do: error()
+  def create_for_rectangle(_i, _r), do: error()
+  def create_similar(_i, _w, _h), do: error()
+  def create_similar_image(_i, _f, _w, _h), do: error()
 
   def set_document_unit(_i, _u), do: error()
+  def set_device_scale(_i, _sx, _sy), do: error()
+  def device_scale(_i), do: error()
+  def set_device_offset(_i, _x, _y), do: error()
+  def device_offset(_i), do: error()
+  def set_fallback_resolution(_i, _x, _y), do: error()
+  def fallback_resolution(_i), do: error()
 
   def save_image(_i, _f), do: error()
+  def image_format(_i), do: error()
+  def image_info(_i), do: error()
 
-  def set_radial_gradient_mask(_i, _g), do: error()
-  def set_linear_gradient_mask(_i, _g), do: error()
-  def set_mesh_mask(_i, _m), do: error()
-  def set_solid_pattern_mask(_i, _m), do: error()
+  def status(_i), do: error()
+
+  def new_recording(_e), do: error()
+  def replay(_i, _r, _o, _sx, _sy), do: error()
+  def ink_extents(_r), do: error()
+
+  def image_data(_i), do: error()
+  def get_pixel(_i, _x, _y), do: error()
+  def set_image_data(_i, _d), do: error()
+
+  def set_source(_i, _p), do: error()
+  def mask(_i, _p), do: error()
+  def pattern_type(_p), do: error()
+  def css_gradient(_css, _r), do: error()
   def mask_surface(_i, _m, _p), do: error()
 
+  def draw_commands(_i, _c), do: error()
+
   def move_to(_i, _p), do: error()
   def line_to(_i, _p), do: error()
   def stroke(_i), do: error()
@@ -31,6 +54,9 @@ defmodule Xairo.Native do
 
   def current_point(_i), do: error()
 
+  def polyline(_i, _p), do: error()
+  def polygon(_i, _p), do: error()
+
   def set_line_width(_i, _w), do: error()
   def set_line_cap(_i, _lc), do: error()
   def set_line_join(_i, _lj), do: error()
@@ -47,20 +73,23 @@ defmodule Xairo.Native do
 
   def rectangle(_i, _r), do: error()
 
-  def set_linear_gradient_source(_i, _lg), do: error()
-  def set_radial_gradient_source(_i, _rg), do: error()
-  def set_mesh_source(_i, _m), do: error()
-  def set_solid_pattern_source(_i, _sp), do: error()
-
   def set_font_size(_i, _s), do: error()
   def show_text(_i, _str), do: error()
   def text_path(_i, _str), do: error()
+  def text_path_along(_i, _p, _f, _str, _o, _a, _so), do: error()
 
   def text_extents(_i, _str), do: error()
   def extents(_i), do: error()
 
   def set_font_face(_i, _f), do: error()
+  def font_face_from_file(_f), do: error()
+  def set_loaded_font_face(_i, _f), do: error()
+  def shape_text(_i, _f, _str, _o), do: error()
+  def show_glyphs(_i, _g), do: error()
+  def glyph_path(_i, _g), do: error()
   def set_font_matrix(_i, _m), do: error()
+  def set_font_options(_i, _o), do: error()
+  def font_options(_i), do: error()
 
   def scale(_i, _sx, _sy), do: error()
   def translate(_i, _dx, _dy), do: error()
@@ -86,6 +115,7 @@ defmodule Xairo.Native do
 
   def copy_path(_i), do: error()
   def copy_path_flat(_i), do: error()
+  def path_to_binary(_p), do: error()
   def append_path(_i, _p), do: error()
   def get_tolerance(_i), do: error()
   def set_tolerance(_i, _t), do: error()
@@ -102,23 +132,69 @@ defmodule Xairo.Native do
   def mesh_corner_color(_m, _p, _c), do: error()
   def mesh_patch_count(_m), do: error()
   def mesh_path(_m, _p), do: error()
+  def mesh_set_matrix(_m, _m), do: error()
+  def mesh_matrix(_m), do: error()
+  def mesh_set_extend(_m, _e), do: error()
+  def mesh_extend(_m), do: error()
+  def mesh_set_filter(_m, _f), do: error()
+  def mesh_filter(_m), do: error()
+  def mesh_from_patches(_p), do: error()
+  def mesh_patches(_m), do: error()
+  def conic_gradient_new(_c, _r, _a, _s), do: error()
 
   def linear_gradient_new(_p1, _p2), do: error()
   def linear_gradient_add_color_stop(_g, _o, _c), do: error()
   def linear_gradient_color_stop_count(_g), do: error()
   def linear_gradient_color_stop(_g, _i), do: error()
+  def linear_gradient_color_stops(_g), do: error()
+  def linear_gradient_set_color_stops(_g, _s), do: error()
   def linear_gradient_linear_points(_g), do: error()
+  def linear_gradient_set_matrix(_g, _m), do: error()
+  def linear_gradient_matrix(_g), do: error()
+  def linear_gradient_set_extend(_g, _e), do: error()
+  def linear_gradient_extend(_g), do: error()
+  def linear_gradient_set_filter(_g, _f), do: error()
+  def linear_gradient_filter(_g), do: error()
 
   def radial_gradient_new(_p1, _r1, _p2, _r2), do: error()
   def radial_gradient_add_color_stop(_g, _o, _c), do: error()
   def radial_gradient_color_stop_count(_g), do: error()
   def radial_gradient_color_stop(_g, _i), do: error()
+  def radial_gradient_color_stops(_g), do: error()
+  def radial_gradient_set_color_stops(_g, _s), do: error()
   def radial_gradient_radial_circles(_g), do: error()
+  def radial_gradient_set_matrix(_g, _m), do: error()
+  def radial_gradient_matrix(_g), do: error()
+  def radial_gradient_set_extend(_g, _e), do: error()
+  def radial_gradient_extend(_g), do: error()
+  def radial_gradient_set_filter(_g, _f), do: error()
+  def radial_gradient_filter(_g), do: error()
 
   def solid_pattern_from_rgba(_c), do: error()
   def solid_pattern_color(_p), do: error()
-
-  def set_surface_pattern_source(_i, _p), do: error()
+  def solid_pattern_set_matrix(_p, _m), do: error()
+  def solid_pattern_matrix(_p), do: error()
+  def solid_pattern_set_extend(_p, _e), do: error()
+  def solid_pattern_extend(_p), do: error()
+  def solid_pattern_set_filter(_p, _f), do: error()
+  def solid_pattern_filter(_p), do: error()
+
+  def surface_pattern_create(_s), do: error()
+  def surface_pattern_surface(_p), do: error()
+  def surface_pattern_set_matrix(_p, _m), do: error()
+  def surface_pattern_matrix(_p), do: error()
+  def surface_pattern_set_extend(_p, _e), do: error()
+  def surface_pattern_extend(_p), do: error()
+  def surface_pattern_set_filter(_p, _f), do: error()
+  def surface_pattern_filter(_p), do: error()
+
+  def raster_pattern_new(_w, _h, _d), do: error()
+  def raster_pattern_set_matrix(_p, _m), do: error()
+  def raster_pattern_matrix(_p), do: error()
+  def raster_pattern_set_extend(_p, _e), do: error()
+  def raster_pattern_extend(_p), do: error()
+  def raster_pattern_set_filter(_p, _f), do: error()
+  def raster_pattern_filter(_p), do: error()
 
   defp error, do: :erlang.nif_error(:nif_not_loaded)
 end
@@ -18,6 +18,29 @@ defmodule Xairo.Path do
     %__MODULE__{resource: path}
   end
 
+  @doc """
+  Returns the points of the path as a list with one binary per subpath, each
+  holding packed little-endian 64-bit float x/y pairs, the same format accepted
+  by `Xairo.polyline/2`.
+
+  Curves are flattened by cairo at its default tolerance; for a different
+  precision, call this on a path returned by `flat/2`. A closed subpath ends
+  with its starting point again, so that passing it to `Xairo.polyline/2`
+  traces its whole outline.
+
+  The points can be unpacked with
+
+  ```
+  for subpath <- Xairo.Path.to_binary(path) do
+    for <<x::little-float-64, y::little-float-64 <- subpath>>, do: {x, y}
+  end
+  ```
+  """
+  @spec to_binary(__MODULE__.t()) :: [binary()] | Xairo.error()
+  def to_binary(%__MODULE__{resource: resource}) do
+    with {:ok, binaries} <- Xairo.Native.path_to_binary(resource), do: binaries
+  end
+
   @doc """
   Calls `Xairo.copy_path_flat/1` on the given image, temporarily setting the
   image `tolerance`.
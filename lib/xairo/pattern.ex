@@ -1,18 +1,197 @@
 defmodule Xairo.Pattern do
   @moduledoc """
-  Shared type definitions for Patterns.
+  Shared type definitions and functions for Patterns.
+
+  ## Extend and filter
+
+  Every pattern has an extend mode, which determines how it is drawn outside
+  of its natural area (beyond the ends of a gradient, or the edges of a
+  surface), and a filter, which determines how its colors are sampled when
+  it is drawn at a different scale.
+
+  The extend modes are
+
+  * `:none`: nothing is drawn outside the pattern's area (the default for
+    surface and raster patterns)
+  * `:repeat`: the pattern is tiled, or a gradient's colors repeat
+  * `:reflect`: like `:repeat`, but alternate copies are mirrored
+  * `:pad`: the colors at the pattern's edges are extended (the default for
+    gradients)
+
+  The filters are `:fast`, `:good` (the default), `:best`, `:nearest`,
+  `:bilinear`, and `:gaussian`. `:nearest` keeps the edges of scaled pixels
+  crisp, e.g. for pixel art.
+
+  ## Example
+
+      iex> LinearGradient.new({0, 0}, {10, 0})
+      ...> |> LinearGradient.add_color_stop(0, RGBA.new(1, 0, 0))
+      ...> |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 1))
+      ...> |> Pattern.set_extend(:repeat)
+
   """
 
+  alias Xairo.Pattern.{LinearGradient, Mesh, RadialGradient, Raster, Solid, Surface}
+
   @typedoc """
-  Represents a color set a percentage of the way along a linear or radial gradient.
+  Represents a color set a percentage of the way along a linear or radial
+  gradient, as `{offset, color}`.
   """
-  @type color_stop :: {Xairo.RGBA.t(), number()}
+  @type color_stop :: {number(), Xairo.RGBA.t()}
 
   @typedoc """
   Specifies the existing types of patterns.
   """
   @type pattern ::
-          Xairo.Pattern.LinearGradient.t()
-          | Xairo.Pattern.RadialGradient.t()
-          | Xairo.Pattern.Mesh.t()
+          LinearGradient.t()
+          | RadialGradient.t()
+          | Mesh.t()
+          | Solid.t()
+          | Surface.t()
+          | Raster.t()
+
+  @typedoc """
+  The kind of a pattern, as returned by `type/1`.
+  """
+  @type type :: :linear_gradient | :radial_gradient | :mesh | :solid | :surface | :raster
+
+  @typedoc """
+  How a pattern is drawn outside of its natural area.
+  """
+  @type extend :: :none | :repeat | :reflect | :pad
+
+  @typedoc """
+  How a pattern's colors are sampled when it is drawn at a different scale.
+  """
+  @type filter :: :fast | :good | :best | :nearest | :bilinear | :gaussian
+
+  @doc """
+  Returns true if the term is any kind of pattern.
+
+  Allowed in guard clauses.
+  """
+  defguard is_pattern(term)
+           when is_struct(term, LinearGradient) or is_struct(term, RadialGradient) or
+                  is_struct(term, Mesh) or is_struct(term, Solid) or is_struct(term, Surface) or
+                  is_struct(term, Raster)
+
+  @doc """
+  Returns the kind of the pattern.
+
+  ## Example
+
+      iex> Mesh.new() |> Pattern.type()
+      :mesh
+
+  """
+  @spec type(pattern()) :: type() | Xairo.error()
+  def type(pattern) when is_pattern(pattern) do
+    with {:ok, type} <- Xairo.Native.pattern_type(pattern.pattern), do: type
+  end
+
+  @doc """
+  Builds a linear or radial gradient from a CSS gradient function, sized to
+  fill the given rectangle as it would fill an element with the same bounds.
+
+  Supports `linear-gradient()`, `radial-gradient()` and their `repeating-`
+  variants, which are returned with a `:repeat` extend mode. Colors can be
+  given as hex values, `rgb()`/`rgba()`, `hsl()`/`hsla()` or basic color
+  names, and positions as percentages or pixels.
+
+  Elliptical radial gradients are drawn by scaling a circular gradient with
+  the pattern's matrix, so setting a new matrix on them with `set_matrix/2`
+  replaces that scaling.
+
+  Returns `{:error, {:css_gradient, message}}` if the string cannot be parsed.
+
+  ## Examples
+
+      iex> Pattern.from_css(
+      ...>   "linear-gradient(45deg, #f00 0%, rgba(0, 0, 255, .5) 100%)",
+      ...>   Rectangle.new({0, 0}, 100, 100)
+      ...> )
+
+      iex> Pattern.from_css(
+      ...>   "radial-gradient(circle at 30% 30%, white, navy)",
+      ...>   Rectangle.new({0, 0}, 100, 100)
+      ...> )
+
+  """
+  @spec from_css(String.t(), Xairo.Rectangle.t()) ::
+          LinearGradient.t() | RadialGradient.t() | Xairo.error()
+  def from_css(css, %Xairo.Rectangle{} = rectangle) when is_binary(css) do
+    with {:ok, pattern} <- Xairo.Native.css_gradient(css, rectangle),
+         {:ok, type} <- Xairo.Native.pattern_type(pattern) do
+      case type do
+        :linear_gradient -> %LinearGradient{pattern: pattern}
+        :radial_gradient -> %RadialGradient{pattern: pattern}
+      end
+    end
+  end
+
+  @doc """
+  Sets the extend mode of the pattern.
+  """
+  @spec set_extend(pattern(), extend()) :: pattern() | Xairo.error()
+  def set_extend(pattern, extend) do
+    with {:ok, _} <- native(pattern, :set_extend, [extend]), do: pattern
+  end
+
+  @doc """
+  Returns the extend mode of the pattern.
+  """
+  @spec extend(pattern()) :: extend() | Xairo.error()
+  def extend(pattern) do
+    with {:ok, extend} <- native(pattern, :extend, []), do: extend
+  end
+
+  @doc """
+  Sets the filter used when drawing the pattern.
+  """
+  @spec set_filter(pattern(), filter()) :: pattern() | Xairo.error()
+  def set_filter(pattern, filter) do
+    with {:ok, _} <- native(pattern, :set_filter, [filter]), do: pattern
+  end
+
+  @doc """
+  Returns the filter used when drawing the pattern.
+  """
+  @spec filter(pattern()) :: filter() | Xairo.error()
+  def filter(pattern) do
+    with {:ok, filter} <- native(pattern, :filter, []), do: filter
+  end
+
+  @doc """
+  Sets the pattern's transformation matrix.
+
+  The matrix maps userspace to the pattern's own space, so a pattern drawn
+  with a matrix scaled by 2 appears half as large. The matrix must be
+  invertible, or `{:error, {:uninvertible_matrix, nil}}` is returned.
+
+  Pattern matrices transform a pattern independently of the path it fills,
+  e.g. to rotate or skew a gradient, or to scale a tiled surface.
+  """
+  @spec set_matrix(pattern(), Xairo.Matrix.t()) :: pattern() | Xairo.error()
+  def set_matrix(pattern, %Xairo.Matrix{} = matrix) do
+    with {:ok, _} <- native(pattern, :set_matrix, [matrix]), do: pattern
+  end
+
+  @doc """
+  Returns the pattern's transformation matrix.
+  """
+  @spec matrix(pattern()) :: Xairo.Matrix.t() | Xairo.error()
+  def matrix(pattern) do
+    with {:ok, matrix} <- native(pattern, :matrix, []), do: matrix
+  end
+
+  defp native(%{pattern: resource} = pattern, function, args) do
+    apply(Xairo.Native, :"#{prefix(pattern)}_#{function}", [resource | args])
+  end
+
+  defp prefix(%LinearGradient{}), do: "linear_gradient"
+  defp prefix(%RadialGradient{}), do: "radial_gradient"
+  defp prefix(%Mesh{}), do: "mesh"
+  defp prefix(%Solid{}), do: "solid_pattern"
+  defp prefix(%Surface{}), do: "surface_pattern"
+  defp prefix(%Raster{}), do: "raster_pattern"
 end
@@ -77,15 +77,33 @@ defmodule Xairo.Pattern.LinearGradient do
   path, will result in a solid color source
 
   """
-  @spec new(Xairo.point(), Xairo.point()) :: __MODULE__.t()
+  @spec new(Xairo.point(), Xairo.point()) :: __MODULE__.t() | Xairo.error()
   def new(start_point, stop_point) do
-    %__MODULE__{
-      pattern:
-        Xairo.Native.linear_gradient_new(
-          Point.from(start_point),
-          Point.from(stop_point)
-        )
-    }
+    with {:ok, pattern} <-
+           Xairo.Native.linear_gradient_new(
+             Point.from(start_point),
+             Point.from(stop_point)
+           ),
+         do: %__MODULE__{pattern: pattern}
+  end
+
+  @doc """
+  Creates a new `t:Xairo.Pattern.LinearGradient.t/0` with the given color
+  stops, as a list of `{offset, color}` tuples.
+
+  ## Example
+
+      iex> LinearGradient.new({0, 0}, {100, 0}, [
+      ...>   {0, RGBA.new(1, 0, 0)},
+      ...>   {1, RGBA.new(0, 0, 1)}
+      ...> ])
+
+  """
+  @spec new(Xairo.point(), Xairo.point(), [Xairo.Pattern.color_stop()]) ::
+          __MODULE__.t() | Xairo.error()
+  def new(start_point, stop_point, stops) when is_list(stops) do
+    with %__MODULE__{} = gradient <- new(start_point, stop_point),
+         do: set_color_stops(gradient, stops)
   end
 
   @doc """
@@ -107,10 +125,11 @@ defmodule Xairo.Pattern.LinearGradient do
       ...> |> LinearGradient.add_color_stop(0.75, red)
 
   """
-  @spec add_color_stop(__MODULE__.t(), number(), RGBA.t()) :: __MODULE__.t()
+  @spec add_color_stop(__MODULE__.t(), number(), RGBA.t()) :: __MODULE__.t() | Xairo.error()
   def add_color_stop(%__MODULE__{} = gradient, offset, %RGBA{} = color) do
-    Xairo.Native.linear_gradient_add_color_stop(gradient.pattern, offset / 1, color)
-    gradient
+    with {:ok, _} <-
+           Xairo.Native.linear_gradient_add_color_stop(gradient.pattern, offset / 1, color),
+         do: gradient
   end
 
   @doc """
@@ -123,14 +142,49 @@ defmodule Xairo.Pattern.LinearGradient do
   end
 
   @doc """
-    Returns the color stop for the gradient at the given index.
+    Returns the color stop for the gradient at the given index, as an
+    `{offset, color}` tuple.
 
     If no color stop exists at the given index, returns an error.
   """
-  @spec color_stop(__MODULE__.t(), integer) :: RGBA.t() | Xairo.error()
+  @spec color_stop(__MODULE__.t(), integer) :: Xairo.Pattern.color_stop() | Xairo.error()
   def color_stop(%__MODULE__{} = gradient, index) do
-    with {:ok, color} <- Xairo.Native.linear_gradient_color_stop(gradient.pattern, index),
-         do: color
+    with {:ok, stop} <- Xairo.Native.linear_gradient_color_stop(gradient.pattern, index),
+         do: stop
+  end
+
+  @doc """
+    Returns all of the gradient's color stops, as `{offset, color}` tuples
+    ordered by offset.
+  """
+  @spec color_stops(__MODULE__.t()) :: [Xairo.Pattern.color_stop()] | Xairo.error()
+  def color_stops(%__MODULE__{pattern: pattern}) do
+    with {:ok, stops} <- Xairo.Native.linear_gradient_color_stops(pattern), do: stops
+  end
+
+  @doc """
+    Returns a new gradient with the same geometry, matrix, extend and filter
+    as the given one, but with `stops` as its color stops.
+
+    cairo only allows appending color stops to a gradient, so this is how
+    existing stops are changed or removed. The original gradient is unchanged.
+
+  ## Example
+
+  Replacing every color stop of a gradient
+
+      iex> LinearGradient.new({0, 0}, {100, 0})
+      ...> |> LinearGradient.add_color_stop(0, RGBA.new(1, 0, 0))
+      ...> |> LinearGradient.set_color_stops([{0, RGBA.new(0, 0, 1)}, {1, RGBA.new(0, 1, 0)}])
+
+  """
+  @spec set_color_stops(__MODULE__.t(), [Xairo.Pattern.color_stop()]) ::
+          __MODULE__.t() | Xairo.error()
+  def set_color_stops(%__MODULE__{pattern: pattern}, stops) when is_list(stops) do
+    stops = Enum.map(stops, fn {offset, %RGBA{} = color} -> {offset / 1, color} end)
+
+    with {:ok, pattern} <- Xairo.Native.linear_gradient_set_color_stops(pattern, stops),
+         do: %__MODULE__{pattern: pattern}
   end
 
   @doc """
@@ -89,7 +89,7 @@ defmodule Xairo.Pattern.Mesh do
   Xairo.set_source(image, mesh)
   ```
   """
-  alias Xairo.{Point, RGBA}
+  alias Xairo.{Pattern.Mesh.Patch, Point, RGBA}
 
   defstruct [:pattern]
 
@@ -100,11 +100,76 @@ defmodule Xairo.Pattern.Mesh do
   @doc """
     Initialize a new mesh.
   """
-  @spec new() :: __MODULE__.t()
+  @spec new() :: __MODULE__.t() | Xairo.error()
   def new do
-    %__MODULE__{
-      pattern: Xairo.Native.mesh_new()
-    }
+    with {:ok, pattern} <- Xairo.Native.mesh_new(), do: %__MODULE__{pattern: pattern}
+  end
+
+  @doc """
+    Builds a mesh approximating a conic (sweep) gradient, whose colors change
+    with the angle around `center` rather than the distance from it, e.g. for
+    a color wheel or pie chart.
+
+    cairo has no conic gradients, so the mesh is made of wedge-shaped patches
+    around the center, reaching out to `radius`. Nothing is drawn beyond it.
+
+    Stop offsets are fractions of a full turn, measured clockwise from
+    `start_angle` (in radians, with 0 pointing along the positive x axis).
+    The first and last colors extend to the start and end of the turn.
+
+  ## Example
+
+  A color wheel, blending from red back to red through green and blue
+
+      iex> Mesh.conic_gradient({50, 50}, 50, 0, [
+      ...>   {0, RGBA.new(1, 0, 0)},
+      ...>   {1 / 3, RGBA.new(0, 1, 0)},
+      ...>   {2 / 3, RGBA.new(0, 0, 1)},
+      ...>   {1, RGBA.new(1, 0, 0)}
+      ...> ])
+
+  """
+  @spec conic_gradient(Xairo.point(), number(), number(), [Xairo.Pattern.color_stop()]) ::
+          __MODULE__.t() | Xairo.error()
+  def conic_gradient(center, radius, start_angle, stops)
+      when is_number(radius) and is_number(start_angle) and is_list(stops) do
+    stops = Enum.map(stops, fn {offset, %RGBA{} = color} -> {offset / 1, color} end)
+
+    with {:ok, pattern} <-
+           Xairo.Native.conic_gradient_new(
+             Point.from(center),
+             radius / 1,
+             start_angle / 1,
+             stops
+           ),
+         do: %__MODULE__{pattern: pattern}
+  end
+
+  @doc """
+    Builds a mesh from a list of `Xairo.Pattern.Mesh.Patch` structs in a
+    single call, instead of building each patch with `begin_patch/1`,
+    `move_to/2` and the rest.
+
+    If a patch is invalid, returns a `{:mesh_patch, {index, reason}}` error
+    identifying it.
+
+  ## Example
+
+      iex> Mesh.from_patches([
+      ...>   Mesh.Patch.new(
+      ...>     {0, 0},
+      ...>     [{100, 0}, {100, 100}, {0, 100}],
+      ...>     [RGBA.new(1, 0, 0), RGBA.new(0, 1, 0), RGBA.new(0, 0, 1), RGBA.new(1, 1, 0)]
+      ...>   )
+      ...> ])
+      ...> |> Mesh.patch_count()
+      1
+
+  """
+  @spec from_patches([Patch.t()]) :: __MODULE__.t() | Xairo.error()
+  def from_patches(patches) when is_list(patches) do
+    with {:ok, pattern} <- Xairo.Native.mesh_from_patches(patches),
+         do: %__MODULE__{pattern: pattern}
   end
 
   @doc """
@@ -114,11 +179,12 @@ defmodule Xairo.Pattern.Mesh do
     is used as a source, the patches are rendered in order
     from bottom up, so the most recently defined patch is on
     top.
+
+    Returns an error if a patch is already in progress.
   """
-  @spec begin_patch(__MODULE__.t()) :: __MODULE__.t()
+  @spec begin_patch(__MODULE__.t()) :: __MODULE__.t() | Xairo.error()
   def begin_patch(%__MODULE__{} = mesh) do
-    Xairo.Native.mesh_begin_patch(mesh.pattern)
-    mesh
+    with {:ok, _} <- Xairo.Native.mesh_begin_patch(mesh.pattern), do: mesh
   end
 
   @doc """
@@ -126,11 +192,14 @@ defmodule Xairo.Pattern.Mesh do
 
     For a mesh pattern to be considered valid, it must have matched
     pairs of `begin_patch/1` and `end_patch/1`.
+
+    Returns an error if no patch is in progress, if the patch has no
+    points, or if cairo reports the mesh as invalid once the patch is
+    complete.
   """
-  @spec end_patch(__MODULE__.t()) :: __MODULE__.t()
+  @spec end_patch(__MODULE__.t()) :: __MODULE__.t() | Xairo.error()
   def end_patch(%__MODULE__{} = mesh) do
-    Xairo.Native.mesh_end_patch(mesh.pattern)
-    mesh
+    with {:ok, _} <- Xairo.Native.mesh_end_patch(mesh.pattern), do: mesh
   end
 
   @doc """
@@ -140,37 +209,44 @@ defmodule Xairo.Pattern.Mesh do
     the initial point is set, the subsequent points will be defined via
     calls to `line_to/2` or `curve_to/2`.
 
+    Returns an error if no patch is in progress, or if the patch already
+    has a side.
   """
-  @spec move_to(__MODULE__.t(), Xairo.point()) :: __MODULE__.t()
+  @spec move_to(__MODULE__.t(), Xairo.point()) :: __MODULE__.t() | Xairo.error()
   def move_to(%__MODULE__{} = mesh, point) do
-    Xairo.Native.mesh_move_to(mesh.pattern, Point.from(point))
-    mesh
+    with {:ok, _} <- Xairo.Native.mesh_move_to(mesh.pattern, Point.from(point)), do: mesh
   end
 
   @doc """
     Creates a side of the current patch defined by a straight line from
     the current point to the prowided point.
+
+    Returns an error if no patch is in progress, or if the patch already
+    has 4 sides.
   """
-  @spec line_to(__MODULE__.t(), Xairo.point()) :: __MODULE__.t()
+  @spec line_to(__MODULE__.t(), Xairo.point()) :: __MODULE__.t() | Xairo.error()
   def line_to(%__MODULE__{} = mesh, point) do
-    Xairo.Native.mesh_line_to(mesh.pattern, Point.from(point))
-    mesh
+    with {:ok, _} <- Xairo.Native.mesh_line_to(mesh.pattern, Point.from(point)), do: mesh
   end
 
   @doc """
     Creates a side of the current patch defined by a Bézier curve
     calculated from the current point and the three provided points.
+
+    Returns an error if no patch is in progress, or if the patch already
+    has 4 sides.
   """
-  @spec curve_to(__MODULE__.t(), Xairo.point(), Xairo.point(), Xairo.point()) :: __MODULE__.t()
+  @spec curve_to(__MODULE__.t(), Xairo.point(), Xairo.point(), Xairo.point()) ::
+          __MODULE__.t() | Xairo.error()
   def curve_to(%__MODULE__{} = mesh, point1, point2, point3) do
-    Xairo.Native.mesh_curve_to(
-      mesh.pattern,
-      Point.from(point1),
-      Point.from(point2),
-      Point.from(point3)
-    )
-
-    mesh
+    with {:ok, _} <-
+           Xairo.Native.mesh_curve_to(
+             mesh.pattern,
+             Point.from(point1),
+             Point.from(point2),
+             Point.from(point3)
+           ),
+         do: mesh
   end
 
   @doc """
@@ -179,16 +255,20 @@ defmodule Xairo.Pattern.Mesh do
     When mesh patch sides are defined, default values for the control
     points are calculated, but this function can be used to overwrite
     those defaults to affect how the colors of the mesh are blended.
+
+    Returns an error if `corner` is not between 0 and 3, or if no patch
+    is in progress.
   """
-  @spec set_control_point(__MODULE__.t(), integer(), Xairo.point()) :: __MODULE__.t()
+  @spec set_control_point(__MODULE__.t(), integer(), Xairo.point()) ::
+          __MODULE__.t() | Xairo.error()
   def set_control_point(%__MODULE__{} = mesh, corner, point) do
-    Xairo.Native.mesh_set_control_point(
-      mesh.pattern,
-      corner,
-      Point.from(point)
-    )
-
-    mesh
+    with {:ok, _} <-
+           Xairo.Native.mesh_set_control_point(
+             mesh.pattern,
+             corner,
+             Point.from(point)
+           ),
+         do: mesh
   end
 
   @doc """
@@ -204,16 +284,14 @@ defmodule Xairo.Pattern.Mesh do
 
   @doc """
     Sets the color for the given corner of the current patch.
+
+    Returns an error if `corner` is not between 0 and 3, or if no patch
+    is in progress.
   """
-  @spec set_corner_color(__MODULE__.t(), integer(), RGBA.t()) :: __MODULE__.t()
+  @spec set_corner_color(__MODULE__.t(), integer(), RGBA.t()) :: __MODULE__.t() | Xairo.error()
   def set_corner_color(%__MODULE__{} = mesh, corner, color) do
-    Xairo.Native.mesh_set_corner_color(
-      mesh.pattern,
-      corner,
-      color
-    )
-
-    mesh
+    with {:ok, _} <- Xairo.Native.mesh_set_corner_color(mesh.pattern, corner, color),
+         do: mesh
   end
 
   @doc """
@@ -247,4 +325,18 @@ defmodule Xairo.Pattern.Mesh do
     with {:ok, path} <- Xairo.Native.mesh_path(mesh.pattern, patch_num),
          do: Xairo.Path.new(path)
   end
+
+  @doc """
+    Returns the patches of the mesh as `Xairo.Pattern.Mesh.Patch` structs,
+    which can be passed to `from_patches/1` to rebuild it.
+
+    cairo stores every side of a patch as a curve, so sides drawn with
+    `line_to/2` are returned as `Xairo.Curve` structs along the same line.
+    All 4 control points and corner colors are returned, including
+    calculated defaults.
+  """
+  @spec patches(__MODULE__.t()) :: [Patch.t()] | Xairo.error()
+  def patches(%__MODULE__{} = mesh) do
+    with {:ok, patches} <- Xairo.Native.mesh_patches(mesh.pattern), do: patches
+  end
 end
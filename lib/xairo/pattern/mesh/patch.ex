@@ -0,0 +1,68 @@
+defmodule Xairo.Pattern.Mesh.Patch do
+  @moduledoc """
+  Describes a complete patch of a `Xairo.Pattern.Mesh`, for building a mesh
+  in a single call with `Xairo.Pattern.Mesh.from_patches/1`.
+
+  A patch is defined by
+
+  - `start`: the first point of the patch (corner C0)
+  - `sides`: up to 4 sides, each a `t:Xairo.Point.t/0` for a straight line
+    to that point, or a `t:Xairo.Curve.t/0` for a Bézier curve. As with
+    `Xairo.Pattern.Mesh.end_patch/1`, a patch with fewer than 4 sides is
+    closed with straight lines back to `start`.
+  - `control_points`: up to 4 control points, indexed by corner. A `nil`
+    control point, or one left off the end of the list, keeps the default
+    calculated from the sides.
+  - `corner_colors`: exactly 4 colors, indexed by corner.
+
+  Curve points are always absolute, even if the curve was created with
+  `relative: true`.
+  """
+  alias Xairo.{Curve, Point, RGBA}
+
+  defstruct [:start, sides: [], control_points: [], corner_colors: []]
+
+  @type side :: Point.t() | Curve.t()
+
+  @type t :: %__MODULE__{
+          start: Point.t(),
+          sides: [side()],
+          control_points: [Point.t() | nil],
+          corner_colors: [RGBA.t()]
+        }
+
+  @doc """
+  Creates a patch from its starting point, sides and corner colors.
+
+  Points can be given as `t:Xairo.Point.t/0` structs or coordinate tuples.
+  Control points can be given with the `control_points` option.
+
+  ## Example
+
+      iex> Patch.new(
+      ...>   {0, 0},
+      ...>   [{100, 0}, {100, 100}, {0, 100}],
+      ...>   [RGBA.new(1, 0, 0), RGBA.new(0, 1, 0), RGBA.new(0, 0, 1), RGBA.new(1, 1, 0)],
+      ...>   control_points: [{20, 20}]
+      ...> ).control_points
+      [%Point{x: 20.0, y: 20.0}]
+
+  """
+  @spec new(Xairo.point(), [side() | Xairo.coordinate()], [RGBA.t()], keyword()) ::
+          __MODULE__.t()
+  def new(start, sides, corner_colors, opts \\ [])
+      when is_list(sides) and is_list(corner_colors) do
+    %__MODULE__{
+      start: Point.from(start),
+      sides: Enum.map(sides, &side_from/1),
+      control_points: opts |> Keyword.get(:control_points, []) |> Enum.map(&control_point_from/1),
+      corner_colors: corner_colors
+    }
+  end
+
+  defp side_from(%Curve{} = curve), do: curve
+  defp side_from(point), do: Point.from(point)
+
+  defp control_point_from(nil), do: nil
+  defp control_point_from(point), do: Point.from(point)
+end
@@ -101,19 +101,39 @@ defmodule Xairo.Pattern.RadialGradient do
           number(),
           Xairo.point(),
           number()
-        ) :: __MODULE__.t()
+        ) :: __MODULE__.t() | Xairo.error()
 
   def new(first_center, first_radius, second_center, second_radius)
       when is_number(first_radius) and is_number(second_radius) do
-    %__MODULE__{
-      pattern:
-        Xairo.Native.radial_gradient_new(
-          Point.from(first_center),
-          first_radius / 1,
-          Point.from(second_center),
-          second_radius / 1
-        )
-    }
+    with {:ok, pattern} <-
+           Xairo.Native.radial_gradient_new(
+             Point.from(first_center),
+             first_radius / 1,
+             Point.from(second_center),
+             second_radius / 1
+           ),
+         do: %__MODULE__{pattern: pattern}
+  end
+
+  @doc """
+  Creates a new `t:Xairo.Pattern.RadialGradient.t/0` with the given color
+  stops, as a list of `{offset, color}` tuples.
+
+  ## Example
+
+      iex> RadialGradient.new({50, 50}, 10, {50, 50}, 80, [
+      ...>   {0, RGBA.new(0.5, 0, 1)},
+      ...>   {1, RGBA.new(0, 0, 1)}
+      ...> ])
+
+  """
+  @spec new(Xairo.point(), number(), Xairo.point(), number(), [Xairo.Pattern.color_stop()]) ::
+          __MODULE__.t() | Xairo.error()
+  def new(first_center, first_radius, second_center, second_radius, stops)
+      when is_list(stops) do
+    with %__MODULE__{} = gradient <-
+           new(first_center, first_radius, second_center, second_radius),
+         do: set_color_stops(gradient, stops)
   end
 
   @doc """
@@ -135,14 +155,14 @@ defmodule Xairo.Pattern.RadialGradient do
       ...> |> RadialGradient.add_color_stop(0.75, red)
 
   """
-  @spec add_color_stop(__MODULE__.t(), number(), RGBA.t()) :: __MODULE__.t()
+  @spec add_color_stop(__MODULE__.t(), number(), RGBA.t()) :: __MODULE__.t() | Xairo.error()
   def add_color_stop(
         %__MODULE__{pattern: pattern} = gradient,
         position,
         %RGBA{} = color
       ) do
-    Xairo.Native.radial_gradient_add_color_stop(pattern, position / 1, color)
-    gradient
+    with {:ok, _} <- Xairo.Native.radial_gradient_add_color_stop(pattern, position / 1, color),
+         do: gradient
   end
 
   @doc """
@@ -155,14 +175,49 @@ defmodule Xairo.Pattern.RadialGradient do
   end
 
   @doc """
-    Returns the color stop for the gradient at the given index.
+    Returns the color stop for the gradient at the given index, as an
+    `{offset, color}` tuple.
 
     If no color stop exists at the given index, returns an error.
   """
-  @spec color_stop(__MODULE__.t(), integer()) :: RGBA.t() | Xairo.error()
+  @spec color_stop(__MODULE__.t(), integer()) :: Xairo.Pattern.color_stop() | Xairo.error()
   def color_stop(%__MODULE__{pattern: pattern}, index) do
-    with {:ok, color} <- Xairo.Native.radial_gradient_color_stop(pattern, index),
-         do: color
+    with {:ok, stop} <- Xairo.Native.radial_gradient_color_stop(pattern, index),
+         do: stop
+  end
+
+  @doc """
+    Returns all of the gradient's color stops, as `{offset, color}` tuples
+    ordered by offset.
+  """
+  @spec color_stops(__MODULE__.t()) :: [Xairo.Pattern.color_stop()] | Xairo.error()
+  def color_stops(%__MODULE__{pattern: pattern}) do
+    with {:ok, stops} <- Xairo.Native.radial_gradient_color_stops(pattern), do: stops
+  end
+
+  @doc """
+    Returns a new gradient with the same geometry, matrix, extend and filter
+    as the given one, but with `stops` as its color stops.
+
+    cairo only allows appending color stops to a gradient, so this is how
+    existing stops are changed or removed. The original gradient is unchanged.
+
+  ## Example
+
+  Replacing every color stop of a gradient
+
+      iex> RadialGradient.new({50, 50}, 10, {50, 50}, 80)
+      ...> |> RadialGradient.add_color_stop(0, RGBA.new(1, 0, 0))
+      ...> |> RadialGradient.set_color_stops([{0, RGBA.new(0, 0, 1)}, {1, RGBA.new(0, 1, 0)}])
+
+  """
+  @spec set_color_stops(__MODULE__.t(), [Xairo.Pattern.color_stop()]) ::
+          __MODULE__.t() | Xairo.error()
+  def set_color_stops(%__MODULE__{pattern: pattern}, stops) when is_list(stops) do
+    stops = Enum.map(stops, fn {offset, %RGBA{} = color} -> {offset / 1, color} end)
+
+    with {:ok, pattern} <- Xairo.Native.radial_gradient_set_color_stops(pattern, stops),
+         do: %__MODULE__{pattern: pattern}
   end
 
   @doc """
@@ -0,0 +1,83 @@
+defmodule Xairo.Pattern.Raster do
+  @moduledoc """
+    Models a pattern whose pixels are generated in Elixir, e.g. for noise,
+    checkerboards or heatmaps of data.
+
+    A raster pattern is created either from a binary of pixel data, with
+    `new/3`, or from a function called for each pixel, with `from_function/3`.
+    The pixels are copied into the pattern when it is created, since cairo
+    cannot call back into Elixir while drawing.
+
+    Like a surface pattern, one pixel of the pattern covers one unit of
+    userspace, and nothing is drawn outside of it until its extend mode is
+    set with `Xairo.Pattern.set_extend/2`.
+
+    ## Example
+
+    A checkerboard of 10x10 squares, tiled across the image
+
+    ```
+    pattern =
+      Raster.from_function(20, 20, fn x, y ->
+        if div(x, 10) == div(y, 10), do: RGBA.new(0, 0, 0), else: RGBA.new(1, 1, 1)
+      end)
+      |> Pattern.set_extend(:repeat)
+      |> Pattern.set_filter(:nearest)
+
+    Xairo.new_image("test.png", 100, 100)
+    |> Xairo.set_source(pattern)
+    |> Xairo.paint()
+    ```
+  """
+
+  alias Xairo.RGBA
+
+  defstruct [:pattern]
+
+  @type t :: %__MODULE__{
+          pattern: reference()
+        }
+
+  @doc """
+    Creates a pattern `width` pixels wide and `height` pixels high from a
+    binary of pixel data.
+
+    The data holds 4 bytes per pixel, for the red, green, blue and alpha
+    channels in that order, given row by row from the top left. The color
+    channels are not premultiplied by alpha.
+
+    Returns an error if the binary is not `width * height * 4` bytes long.
+
+  ## Example
+
+      iex> Raster.new(2, 1, <<255, 0, 0, 255, 0, 0, 255, 128>>) |> Pattern.type()
+      :raster
+
+  """
+  @spec new(pos_integer(), pos_integer(), binary()) :: __MODULE__.t() | Xairo.error()
+  def new(width, height, data)
+      when is_integer(width) and is_integer(height) and is_binary(data) do
+    with {:ok, pattern} <- Xairo.Native.raster_pattern_new(width, height, data),
+         do: %__MODULE__{pattern: pattern}
+  end
+
+  @doc """
+    Creates a pattern `width` pixels wide and `height` pixels high by
+    calling `fun` with the `x` and `y` coordinates of each pixel, which
+    returns its color as a `Xairo.RGBA` struct.
+  """
+  @spec from_function(pos_integer(), pos_integer(), (integer(), integer() -> RGBA.t())) ::
+          __MODULE__.t() | Xairo.error()
+  def from_function(width, height, fun)
+      when is_integer(width) and is_integer(height) and is_function(fun, 2) do
+    data =
+      for y <- 0..(height - 1)//1, x <- 0..(width - 1)//1, into: <<>> do
+        %RGBA{red: r, green: g, blue: b, alpha: a} = fun.(x, y)
+        <<to_byte(r), to_byte(g), to_byte(b), to_byte(a)>>
+      end
+
+    new(width, height, data)
+  end
+
+  defp to_byte(channel), do: round(channel * 255)
+end
@@ -30,11 +30,10 @@ defmodule Xairo.Pattern.Solid do
           pattern: reference()
         }
 
-  @spec new(RGBA.t()) :: __MODULE__.t()
+  @spec new(RGBA.t()) :: __MODULE__.t() | Xairo.error()
   def new(%RGBA{} = color) do
-    %__MODULE__{
-      pattern: Xairo.Native.solid_pattern_from_rgba(color)
-    }
+    with {:ok, pattern} <- Xairo.Native.solid_pattern_from_rgba(color),
+         do: %__MODULE__{pattern: pattern}
   end
 
   @spec color(__MODULE__.t()) :: RGBA.t() | Xairo.error()
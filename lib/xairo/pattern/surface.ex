@@ -51,12 +51,22 @@ defmodule Xairo.Pattern.Surface do
     |> Xairo.rectangle({20, 20}, 30, 40)
     |> Xairo.fill()
     ```
+
+    The struct holds both the surface, as `resource`, so that it can be drawn
+    to like any image, and the native surface pattern, as `pattern`, which
+    keeps its own matrix, extend mode, and filter (see `Xairo.Pattern`) and
+    can be reused as a source or mask for any number of images.
+
+    The pattern's matrix is set when it is created to match the scale of the
+    surface, so that the pattern is drawn at the same size in userspace as
+    the surface. Transforming the surface afterwards does not change it.
   """
 
-  defstruct [:resource]
+  defstruct [:resource, :pattern]
 
   @type t :: %__MODULE__{
-          resource: reference()
+          resource: reference(),
+          pattern: reference()
         }
 
   @doc """
@@ -66,11 +76,78 @@ defmodule Xairo.Pattern.Surface do
     scale = Keyword.get(options, :scale, 1.0) * 1.0
     scaled_width = round(scale * width)
     scaled_height = round(scale * height)
-    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height)
+    format = Keyword.get(options, :format, :argb32)
+    {:ok, resource} = Xairo.Native.new_png_image(scaled_width, scaled_height, format)
     Xairo.Native.scale(resource, scale, scale)
 
-    %__MODULE__{
-      resource: resource
-    }
+    from_resource(resource)
+  end
+
+  @doc """
+    Creates a surface pattern that reads from an existing image, such as a
+    `Xairo.Image.Png`, `Xairo.Mask`, or `Xairo.Recording`.
+
+    Drawing to the returned struct draws to the image.
+  """
+  @spec from_image(Xairo.image() | Xairo.Mask.t()) :: __MODULE__.t() | Xairo.error()
+  def from_image(%{resource: resource}) do
+    from_resource(resource)
+  end
+
+  @doc """
+    Returns the surface the native pattern reads from, as a surface pattern
+    struct with its `resource` read back from the pattern.
+  """
+  @spec surface(__MODULE__.t()) :: __MODULE__.t() | Xairo.error()
+  def surface(%__MODULE__{pattern: pattern} = surface) do
+    with {:ok, resource} <- Xairo.Native.surface_pattern_surface(pattern),
+         do: %{surface | resource: resource}
+  end
+
+  @doc """
+    Creates a new surface pattern with the given width and height that is
+    compatible with `image`, so that it can be drawn onto it as efficiently
+    as possible.
+
+    The surface type is chosen by cairo to match the image's backend, so a
+    pattern created from a PDF image keeps its contents as vector data.
+    Passing a `format` option instead always creates an image surface in the
+    given pixel format (see `Xairo.Image.Png`), optimized for drawing onto
+    the image.
+
+    The `scale` option defaults to the scale of `image`.
+
+    ## Example
+
+    ```
+    pattern = Surface.similar(image, 50, 50)
+    |> Xairo.set_color(RGBA.new(1, 0, 0))
+    |> Xairo.paint()
+
+    image
+    |> Xairo.set_source(pattern)
+    |> Xairo.paint()
+    ```
+  """
+  def similar(%{resource: image} = target, width, height, options \\ []) do
+    scale = Keyword.get(options, :scale, Map.get(target, :scale, 1.0)) * 1.0
+    scaled_width = round(scale * width)
+    scaled_height = round(scale * height)
+
+    result =
+      case Keyword.get(options, :format) do
+        nil -> Xairo.Native.create_similar(image, scaled_width, scaled_height)
+        format -> Xairo.Native.create_similar_image(image, format, scaled_width, scaled_height)
+      end
+
+    with {:ok, resource} <- result do
+      Xairo.Native.scale(resource, scale, scale)
+      from_resource(resource)
+    end
+  end
+
+  defp from_resource(resource) do
+    with {:ok, pattern} <- Xairo.Native.surface_pattern_create(resource),
+         do: %__MODULE__{resource: resource, pattern: pattern}
   end
 end
@@ -0,0 +1,89 @@
+defmodule Xairo.Recording do
+  @moduledoc """
+    Models a cairo recording surface, which records the drawing operations
+    applied to it so that they can be replayed onto any other image.
+
+    A recording can be drawn to with every drawing and transformation
+    function, just like an image, but cannot be saved directly. Instead it
+    is replayed onto PNG, SVG, PDF, or PostScript images with
+    `Xairo.replay/4`, so that a figure drawn once can be output in several
+    formats.
+
+    A recording created with `new/0` is unbounded, while one created with
+    `new/1` or `new/2` only records drawing within the given extents.
+    `ink_extents/1` returns the bounding box of everything drawn to the
+    recording, which can be used to size the image it is replayed onto.
+
+    ## Example
+
+      iex> recording = Recording.new()
+      ...> |> Xairo.arc({50, 50}, 40, 0, 2 * :math.pi())
+      ...> |> Xairo.fill()
+      iex> %Rectangle{width: width, height: height} = Recording.ink_extents(recording)
+      iex> for filename <- ["figure.png", "figure.svg"] do
+      ...>   Xairo.new_image(filename, width, height)
+      ...>   |> Xairo.replay(recording, {-10, -10})
+      ...>   |> Xairo.save_image()
+      ...> end
+
+  """
+  alias Xairo.{Point, Rectangle}
+
+  defstruct [:resource, :extents]
+
+  @type t :: %__MODULE__{
+          resource: reference(),
+          extents: Rectangle.t() | nil
+        }
+
+  @doc """
+    Creates a new unbounded recording.
+  """
+  @spec new() :: __MODULE__.t()
+  def new, do: do_new(nil)
+
+  @doc """
+    Creates a new recording bounded by the given `Xairo.Rectangle`.
+  """
+  @spec new(Rectangle.t()) :: __MODULE__.t()
+  def new(%Rectangle{} = extents), do: do_new(extents)
+
+  @doc """
+    Creates a new recording bounded by a rectangle of the given width and
+    height with its corner at the origin.
+  """
+  @spec new(number(), number()) :: __MODULE__.t()
+  def new(width, height), do: do_new(Rectangle.new({0, 0}, width, height))
+
+  @doc """
+    Returns the bounding box of everything drawn to the recording as a
+    `Xairo.Rectangle`.
+  """
+  @spec ink_extents(__MODULE__.t()) :: Rectangle.t() | Xairo.error()
+  def ink_extents(%__MODULE__{resource: resource}) do
+    with {:ok, %Rectangle{} = extents} <- Xairo.Native.ink_extents(resource), do: extents
+  end
+
+  defp do_new(extents) do
+    {:ok, resource} = Xairo.Native.new_recording(extents)
+
+    %__MODULE__{
+      resource: resource,
+      extents: extents
+    }
+  end
+
+  defimpl Inspect do
+    import Inspect.Algebra
+
+    def inspect(%Xairo.Recording{extents: extents, resource: resource}, opts) do
+      bounds =
+        case extents do
+          nil -> "unbounded"
+          %Rectangle{corner: %Point{x: x, y: y}, width: w, height: h} -> "#{w}x#{h}@(#{x}, #{y})"
+        end
+
+      concat(["#Xairo.Recording<", bounds, " ", to_doc(resource, opts), ">"])
+    end
+  end
+end
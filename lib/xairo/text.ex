@@ -4,7 +4,7 @@ defmodule Xairo.Text do
   """
 
   alias Xairo.Native
-  alias Xairo.Text.Extents
+  alias Xairo.Text.{Extents, FontFace, FontOptions, Glyph, ShapingOptions}
 
   @doc """
   Returns `t:Xairo.Text.Extents.t/0` for `text` in the context of `image`.
@@ -22,4 +22,52 @@ defmodule Xairo.Text do
   def extents(%{resource: _} = image, text) do
     with {:ok, %Extents{} = extents} <- Native.text_extents(image.resource, text), do: extents
   end
+
+  @doc """
+  Returns the `t:Xairo.Text.FontOptions.t/0` currently set for `image`.
+
+  See `Xairo.set_font_options/2`.
+  """
+  @spec font_options(Xairo.image()) :: FontOptions.t() | Xairo.error()
+  def font_options(%{resource: _} = image) do
+    with {:ok, %FontOptions{} = options} <- Native.font_options(image.resource), do: options
+  end
+
+  @doc """
+  Shapes `text` with `font_face`, returning a list of positioned
+  `t:Xairo.Text.Glyph.t/0` structs.
+
+  Shaping applies the font's rules for joining, reordering, and substituting
+  characters, so complex scripts and ligatures are laid out correctly. The
+  glyphs are positioned starting from the image's current point (or the origin,
+  if there is none), and are scaled by the image's current font size.
+
+  The following options can be given to control shaping. Any that are not given
+  are guessed from the text.
+
+  - `direction`: one of `:ltr`, `:rtl`, `:ttb`, or `:btt`
+  - `script`: an ISO 15924 script code, e.g. `"Arab"`
+  - `language`: a BCP 47 language tag, e.g. `"ar"`
+
+  See `Xairo.Text.ShapingOptions` for more details.
+
+  ## Example
+
+  ```
+  face = FontFace.load("/usr/share/fonts/truetype/noto/NotoSansArabic-Regular.ttf")
+
+  image =
+    Xairo.new_image("test.png", 100, 100)
+    |> Xairo.set_font_size(20)
+    |> Xairo.move_to({90, 50})
+
+  Text.shape(image, face, "مرحبا", direction: :rtl, script: "Arab", language: "ar")
+  ```
+  """
+  @spec shape(Xairo.image(), FontFace.t(), String.t(), keyword()) :: [Glyph.t()] | Xairo.error()
+  def shape(%{resource: _} = image, %FontFace{} = font_face, text, opts \\ []) do
+    with %ShapingOptions{} = options <- ShapingOptions.new(opts),
+         {:ok, glyphs} <- Native.shape_text(image.resource, font_face.resource, text, options),
+         do: glyphs
+  end
 end
@@ -0,0 +1,54 @@
+defmodule Xairo.Text.FontFace do
+  @moduledoc """
+  Models a font face loaded from a TrueType or OpenType font file.
+
+  Unlike the "toy" fonts described in `Xairo.Text.Font`, a loaded font face
+  can be used with `Xairo.Text.shape/4` to lay out complex scripts such as
+  Arabic, Hebrew, or Devanagari, and to apply ligatures and other font
+  features, before the resulting glyphs are rendered with
+  `Xairo.show_glyphs/2`.
+
+  ## Example
+
+      iex> face = FontFace.load("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf")
+      iex> Xairo.new_image("text.png", 100, 100)
+      ...> |> Xairo.set_font_face(face)
+      ...> |> Xairo.move_to({10, 50})
+      ...> |> Xairo.show_shaped_text(face, "hello")
+
+  """
+
+  defstruct [:resource, :filename]
+
+  @type t :: %__MODULE__{
+          resource: reference(),
+          filename: String.t()
+        }
+
+  @doc """
+  Loads the font at `filename`.
+
+  Returns an error if the file does not exist or cannot be parsed as a font.
+  """
+  @spec load(String.t()) :: __MODULE__.t() | Xairo.error()
+  def load(filename) do
+    with {:ok, resource} <- Xairo.Native.font_face_from_file(filename) do
+      %__MODULE__{
+        resource: resource,
+        filename: filename
+      }
+    end
+  end
+
+  defimpl Inspect do
+    import Inspect.Algebra
+
+    def inspect(%{filename: filename}, _opts) do
+      concat([
+        "#FontFace<",
+        Path.basename(filename),
+        ">"
+      ])
+    end
+  end
+end
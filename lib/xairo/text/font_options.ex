@@ -0,0 +1,109 @@
+defmodule Xairo.Text.FontOptions do
+  @moduledoc """
+  Models the options cairo uses when rasterizing text.
+
+  By default cairo picks these values based on the surface being drawn to and,
+  for some backends, the system's font configuration. This means raster text
+  can look different from one machine to the next. Setting them explicitly
+  with `Xairo.set_font_options/2` makes text rendering reproducible.
+
+  ## Descriptions of the fields
+
+  - `antialias`: the type of antialiasing applied to glyphs. One of
+    `:default`, `:none`, `:gray`, `:subpixel`, `:fast`, `:good`, or `:best`
+  - `hint_style`: how strongly glyph outlines are fit to the pixel grid. One of
+    `:default`, `:none`, `:slight`, `:medium`, or `:full`
+  - `hint_metrics`: whether font metrics are rounded to integer values. One of
+    `:default`, `:off`, or `:on`
+  - `subpixel_order`: the order of color elements within each pixel, used
+    when `antialias` is `:subpixel`. One of `:default`, `:rgb`, `:bgr`,
+    `:vrgb`, or `:vbgr`
+  """
+  defstruct [
+    :antialias,
+    :hint_style,
+    :hint_metrics,
+    :subpixel_order
+  ]
+
+  @type antialias :: :default | :none | :gray | :subpixel | :fast | :good | :best
+  @type hint_style :: :default | :none | :slight | :medium | :full
+  @type hint_metrics :: :default | :off | :on
+  @type subpixel_order :: :default | :rgb | :bgr | :vrgb | :vbgr
+
+  @type t :: %__MODULE__{
+          antialias: antialias(),
+          hint_style: hint_style(),
+          hint_metrics: hint_metrics(),
+          subpixel_order: subpixel_order()
+        }
+
+  @doc """
+  Creates a new set of font options.
+
+  This function expects a keyword list with values for `:antialias`,
+  `:hint_style`, `:hint_metrics`, and `:subpixel_order`. Any keys that are not
+  given are set to `:default`, leaving the choice to cairo.
+
+  ## Examples
+
+      iex> FontOptions.new()
+      #FontOptions<default, default, default, default>
+
+      iex> FontOptions.new(antialias: :gray, hint_style: :none, hint_metrics: :off)
+      #FontOptions<gray, none, off, default>
+
+  Any unexpected values will return an error tuple. If multiple invalid values are given, only an error for the
+  first invalid value detected will be returned
+
+      iex> FontOptions.new(antialias: :lots, hint_style: :some)
+      {:error, {:invalid_antialias, :lots}}
+  """
+  @spec new(keyword(atom())) :: __MODULE__.t() | Xairo.error()
+  def new(opts \\ []) do
+    with {:ok, antialias} <- get_value(opts, :antialias),
+         {:ok, hint_style} <- get_value(opts, :hint_style),
+         {:ok, hint_metrics} <- get_value(opts, :hint_metrics),
+         {:ok, subpixel_order} <- get_value(opts, :subpixel_order) do
+      %__MODULE__{
+        antialias: antialias,
+        hint_style: hint_style,
+        hint_metrics: hint_metrics,
+        subpixel_order: subpixel_order
+      }
+    end
+  end
+
+  defp get_value(opts, key) do
+    case opts[key] do
+      nil ->
+        {:ok, :default}
+
+      value ->
+        if allowed?(key, value), do: {:ok, value}, else: {:error, {:"invalid_#{key}", value}}
+    end
+  end
+
+  defp allowed?(:antialias, value), do: value in ~w(default none gray subpixel fast good best)a
+  defp allowed?(:hint_style, value), do: value in ~w(default none slight medium full)a
+  defp allowed?(:hint_metrics, value), do: value in ~w(default off on)a
+  defp allowed?(:subpixel_order, value), do: value in ~w(default rgb bgr vrgb vbgr)a
+
+  defimpl Inspect do
+    import Inspect.Algebra
+
+    def inspect(options, _opts) do
+      concat([
+        "#FontOptions<",
+        [
+          options.antialias,
+          options.hint_style,
+          options.hint_metrics,
+          options.subpixel_order
+        ]
+        |> Enum.join(", "),
+        ">"
+      ])
+    end
+  end
+end
@@ -0,0 +1,33 @@
+defmodule Xairo.Text.Glyph do
+  @moduledoc """
+  Models a single positioned glyph.
+
+  Glyphs are returned by `Xairo.Text.shape/4` and can be rendered with
+  `Xairo.show_glyphs/2` or added to the current path with `Xairo.glyph_path/2`.
+
+  ## Descriptions of the fields
+
+  - `index`: the glyph's index in the font face it was shaped with. This is not
+    a Unicode codepoint, and only has meaning for that specific font face
+  - `x` and `y`: the position of the glyph's origin in userspace
+  """
+  defstruct [:index, :x, :y]
+
+  @type t :: %__MODULE__{
+          index: non_neg_integer(),
+          x: number(),
+          y: number()
+        }
+
+  defimpl Inspect do
+    import Inspect.Algebra
+
+    def inspect(%{index: index, x: x, y: y}, _opts) do
+      concat([
+        "#Glyph<",
+        "#{index} @ (#{x}, #{y})",
+        ">"
+      ])
+    end
+  end
+end
@@ -0,0 +1,56 @@
+defmodule Xairo.Text.ShapingOptions do
+  @moduledoc """
+  Models the options used when shaping text with `Xairo.Text.shape/4`.
+
+  All fields are optional. Any field left as `nil` is guessed from the text
+  being shaped.
+
+  ## Descriptions of the fields
+
+  - `direction`: the direction the text is read in, one of `:ltr`, `:rtl`,
+    `:ttb`, or `:btt`
+  - `script`: the ISO 15924 code for the text's script, e.g. `"Arab"`, `"Hebr"`,
+    or `"Deva"`
+  - `language`: the BCP 47 language tag of the text, e.g. `"ar"` or `"hi"`
+  """
+  defstruct [:direction, :script, :language]
+
+  @type direction :: :ltr | :rtl | :ttb | :btt
+
+  @type t :: %__MODULE__{
+          direction: direction() | nil,
+          script: String.t() | nil,
+          language: String.t() | nil
+        }
+
+  @doc """
+  Creates a new set of shaping options from a keyword list.
+
+  ## Examples
+
+      iex> ShapingOptions.new()
+      %ShapingOptions{direction: nil, script: nil, language: nil}
+
+      iex> ShapingOptions.new(direction: :rtl, script: "Arab", language: "ar")
+      %ShapingOptions{direction: :rtl, script: "Arab", language: "ar"}
+
+  An invalid direction returns an error tuple
+
+      iex> ShapingOptions.new(direction: :sideways)
+      {:error, {:invalid_direction, :sideways}}
+  """
+  @spec new(keyword()) :: __MODULE__.t() | Xairo.error()
+  def new(opts \\ []) do
+    direction = Keyword.get(opts, :direction)
+
+    if is_nil(direction) or direction in ~w(ltr rtl ttb btt)a do
+      %__MODULE__{
+        direction: direction,
+        script: Keyword.get(opts, :script),
+        language: Keyword.get(opts, :language)
+      }
+    else
+      {:error, {:invalid_direction, direction}}
+    end
+  end
+end
@@ -39,7 +39,11 @@ defmodule Xairo.MixProject do
           Text: [
             Xairo.Text,
             Xairo.Text.Extents,
-            Xairo.Text.Font
+            Xairo.Text.Font,
+            Xairo.Text.FontFace,
+            Xairo.Text.FontOptions,
+            Xairo.Text.Glyph,
+            Xairo.Text.ShapingOptions
           ],
           Transformation: [
             Xairo.Matrix
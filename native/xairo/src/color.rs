@@ -17,31 +17,34 @@ impl Rgba {
 }
 
 #[rustler::nif]
-fn set_color(image: ImageArc, rgba: Rgba) -> ImageArc {
+fn set_color(image: ImageArc, rgba: Rgba) -> ImageResult {
     let (r, g, b, a) = rgba.to_tuple();
-    image.context.set_source_rgba(r, g, b, a);
-    image
+    image.lock()?.context.set_source_rgba(r, g, b, a);
+    Ok(image)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 fn stroke(image: ImageArc) -> ImageResult {
-    match image.context.stroke() {
+    let result = image.lock()?.context.stroke();
+    match result {
         Ok(_) => Ok(image),
         Err(_) => Err(Error::PathRender("stroke"))
     }
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 fn fill(image: ImageArc) -> ImageResult {
-    match image.context.fill() {
+    let result = image.lock()?.context.fill();
+    match result {
         Ok(_) => Ok(image),
         Err(_) => Err(Error::PathRender("fill"))
     }
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 fn paint(image: ImageArc) -> ImageResult {
-    match image.context.paint() {
+    let result = image.lock()?.context.paint();
+    match result {
         Ok(_) => Ok(image),
         Err(_) => Err(Error::PathRender("paint"))
     }
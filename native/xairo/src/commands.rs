@@ -0,0 +1,185 @@
+use crate::color::Rgba;
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::shapes::{Arc, Curve, Point, Rectangle, Vector};
+use crate::status;
+use crate::xairo_image::{ImageArc, ImageResult};
+use cairo::Context;
+use rustler::{Decoder, NifResult, Term};
+
+/// A single drawing operation in a batch passed to `draw_commands`.
+///
+/// Commands without arguments are given as bare atoms (e.g. `:stroke`), and
+/// commands with arguments as tuples tagged with the command name (e.g.
+/// `{:move_to, %Point{}}` or `{:translate, dx, dy}`).
+#[derive(Copy, Clone, Debug)]
+pub enum DrawCommand {
+    MoveTo(Point),
+    RelMoveTo(Vector),
+    LineTo(Point),
+    RelLineTo(Vector),
+    CurveTo(Curve),
+    RelCurveTo(Curve),
+    Arc(Arc),
+    ArcNegative(Arc),
+    Rectangle(Rectangle),
+    ClosePath,
+    NewPath,
+    NewSubPath,
+    SetColor(Rgba),
+    SetLineWidth(f64),
+    Stroke,
+    Fill,
+    Paint,
+    Save,
+    Restore,
+    Translate(f64, f64),
+    Scale(f64, f64),
+    Rotate(f64),
+    Transform(Matrix),
+    SetMatrix(Matrix),
+    IdentityMatrix,
+}
+
+impl<'a> Decoder<'a> for DrawCommand {
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        let (name, args) = if term.is_atom() {
+            (term.atom_to_string()?, Vec::new())
+        } else {
+            let elements = rustler::types::tuple::get_tuple(term)?;
+            match elements.split_first() {
+                Some((tag, args)) => (tag.atom_to_string()?, args.to_vec()),
+                None => return Err(rustler::Error::BadArg),
+            }
+        };
+
+        match (name.as_str(), args.as_slice()) {
+            ("move_to", [point]) => Ok(DrawCommand::MoveTo(point.decode()?)),
+            ("rel_move_to", [vector]) => Ok(DrawCommand::RelMoveTo(vector.decode()?)),
+            ("line_to", [point]) => Ok(DrawCommand::LineTo(point.decode()?)),
+            ("rel_line_to", [vector]) => Ok(DrawCommand::RelLineTo(vector.decode()?)),
+            ("curve_to", [curve]) => Ok(DrawCommand::CurveTo(curve.decode()?)),
+            ("rel_curve_to", [curve]) => Ok(DrawCommand::RelCurveTo(curve.decode()?)),
+            ("arc", [arc]) => Ok(DrawCommand::Arc(arc.decode()?)),
+            ("arc_negative", [arc]) => Ok(DrawCommand::ArcNegative(arc.decode()?)),
+            ("rectangle", [rectangle]) => Ok(DrawCommand::Rectangle(rectangle.decode()?)),
+            ("close_path", []) => Ok(DrawCommand::ClosePath),
+            ("new_path", []) => Ok(DrawCommand::NewPath),
+            ("new_sub_path", []) => Ok(DrawCommand::NewSubPath),
+            ("set_color", [rgba]) => Ok(DrawCommand::SetColor(rgba.decode()?)),
+            ("set_line_width", [width]) => Ok(DrawCommand::SetLineWidth(width.decode()?)),
+            ("stroke", []) => Ok(DrawCommand::Stroke),
+            ("fill", []) => Ok(DrawCommand::Fill),
+            ("paint", []) => Ok(DrawCommand::Paint),
+            ("save", []) => Ok(DrawCommand::Save),
+            ("restore", []) => Ok(DrawCommand::Restore),
+            ("translate", [dx, dy]) => Ok(DrawCommand::Translate(dx.decode()?, dy.decode()?)),
+            ("scale", [sx, sy]) => Ok(DrawCommand::Scale(sx.decode()?, sy.decode()?)),
+            ("rotate", [rad]) => Ok(DrawCommand::Rotate(rad.decode()?)),
+            ("transform", [matrix]) => Ok(DrawCommand::Transform(matrix.decode()?)),
+            ("set_matrix", [matrix]) => Ok(DrawCommand::SetMatrix(matrix.decode()?)),
+            ("identity_matrix", []) => Ok(DrawCommand::IdentityMatrix),
+            _ => Err(rustler::Error::BadArg),
+        }
+    }
+}
+
+impl DrawCommand {
+    fn run(self, context: &Context) -> Result<(), Error> {
+        match self {
+            DrawCommand::MoveTo(point) => context.move_to(point.x, point.y),
+            DrawCommand::RelMoveTo(vector) => context.rel_move_to(vector.x, vector.y),
+            DrawCommand::LineTo(point) => context.line_to(point.x, point.y),
+            DrawCommand::RelLineTo(vector) => context.rel_line_to(vector.x, vector.y),
+            DrawCommand::CurveTo(curve) => {
+                let ((x1, y1), (x2, y2), (x3, y3)) = curve.to_tuple();
+                context.curve_to(x1, y1, x2, y2, x3, y3);
+            }
+            DrawCommand::RelCurveTo(curve) => {
+                let ((x1, y1), (x2, y2), (x3, y3)) = curve.to_tuple();
+                context.rel_curve_to(x1, y1, x2, y2, x3, y3);
+            }
+            DrawCommand::Arc(arc) => context.arc(
+                arc.center.x,
+                arc.center.y,
+                arc.radius,
+                arc.start_angle,
+                arc.stop_angle,
+            ),
+            DrawCommand::ArcNegative(arc) => context.arc_negative(
+                arc.center.x,
+                arc.center.y,
+                arc.radius,
+                arc.start_angle,
+                arc.stop_angle,
+            ),
+            DrawCommand::Rectangle(rect) => {
+                context.rectangle(rect.corner.x, rect.corner.y, rect.width, rect.height)
+            }
+            DrawCommand::ClosePath => context.close_path(),
+            DrawCommand::NewPath => context.new_path(),
+            DrawCommand::NewSubPath => context.new_sub_path(),
+            DrawCommand::SetColor(rgba) => {
+                let (r, g, b, a) = rgba.to_tuple();
+                context.set_source_rgba(r, g, b, a);
+            }
+            DrawCommand::SetLineWidth(width) => context.set_line_width(width),
+            DrawCommand::Stroke => {
+                if context.stroke().is_err() {
+                    return Err(Error::PathRender("stroke"));
+                }
+            }
+            DrawCommand::Fill => {
+                if context.fill().is_err() {
+                    return Err(Error::PathRender("fill"));
+                }
+            }
+            DrawCommand::Paint => {
+                if context.paint().is_err() {
+                    return Err(Error::PathRender("paint"));
+                }
+            }
+            DrawCommand::Save => {
+                if context.save().is_err() {
+                    return Err(Error::ContextState("save"));
+                }
+            }
+            DrawCommand::Restore => {
+                if context.restore().is_err() {
+                    return Err(Error::ContextState("restore"));
+                }
+            }
+            DrawCommand::Translate(dx, dy) => context.translate(dx, dy),
+            DrawCommand::Scale(sx, sy) => context.scale(sx, sy),
+            DrawCommand::Rotate(rad) => context.rotate(rad),
+            DrawCommand::Transform(matrix) => context.transform(matrix.into()),
+            DrawCommand::SetMatrix(matrix) => context.set_matrix(matrix.into()),
+            DrawCommand::IdentityMatrix => context.identity_matrix(),
+        }
+
+        status::check(context)
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn draw_commands<'a>(image: ImageArc, commands: Vec<Term<'a>>) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        for (index, command) in commands.into_iter().enumerate() {
+            let command: DrawCommand = match command.decode() {
+                Ok(command) => command,
+                Err(_) => {
+                    return Err(Error::DrawCommand(
+                        index,
+                        Box::new(Error::InvalidDrawCommand),
+                    ))
+                }
+            };
+            if let Err(error) = command.run(&guard.context) {
+                return Err(Error::DrawCommand(index, Box::new(error)));
+            }
+        }
+    }
+
+    Ok(image)
+}
@@ -0,0 +1,127 @@
+use crate::color::Rgba;
+use crate::error::Error;
+use crate::gradient::ColorStop;
+use crate::mesh::{MeshArc, XairoMesh};
+use crate::resource::Guarded;
+use crate::shapes::Point;
+use cairo::MeshCorner;
+use rustler::ResourceArc;
+use std::cmp::Ordering;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Patches are kept to at most a quarter turn, so that a single Bézier curve
+/// closely approximates their outer arc.
+const MAX_SWEEP: f64 = FRAC_PI_2;
+
+fn mix(from: Rgba, to: Rgba, t: f64) -> Rgba {
+    let channel = |from: f64, to: f64| from + (to - from) * t;
+    Rgba {
+        red: channel(from.red, to.red),
+        green: channel(from.green, to.green),
+        blue: channel(from.blue, to.blue),
+        alpha: channel(from.alpha, to.alpha),
+    }
+}
+
+/// Sorts the stops by offset, clamped to `[0, 1]`, and extends the first and
+/// last colors to the start and end of the turn.
+fn full_turn(mut stops: Vec<ColorStop>) -> Vec<ColorStop> {
+    for stop in stops.iter_mut() {
+        stop.0 = stop.0.clamp(0.0, 1.0);
+    }
+    stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    if let (Some(&(first, color)), Some(&(last, last_color))) = (stops.first(), stops.last()) {
+        if first > 0.0 {
+            stops.insert(0, (0.0, color));
+        }
+        if last < 1.0 {
+            stops.push((1.0, last_color));
+        }
+    }
+    stops
+}
+
+/// Adds a wedge-shaped patch between the two angles, whose color blends from
+/// `from` to `to` around the center.
+fn add_patch(
+    mesh: &cairo::Mesh,
+    center: Point,
+    radius: f64,
+    (start, from): (f64, Rgba),
+    (end, to): (f64, Rgba),
+) {
+    let point = |angle: f64| {
+        (
+            center.x + radius * angle.cos(),
+            center.y + radius * angle.sin(),
+        )
+    };
+    let (x0, y0) = point(start);
+    let (x3, y3) = point(end);
+    let k = 4.0 / 3.0 * ((end - start) / 4.0).tan() * radius;
+
+    mesh.begin_patch();
+    mesh.move_to(center.x, center.y);
+    mesh.line_to(x0, y0);
+    mesh.curve_to(
+        x0 - k * start.sin(),
+        y0 + k * start.cos(),
+        x3 + k * end.sin(),
+        y3 - k * end.cos(),
+        x3,
+        y3,
+    );
+    mesh.line_to(center.x, center.y);
+
+    for (corner, color) in [
+        (MeshCorner::MeshCorner0, from),
+        (MeshCorner::MeshCorner1, from),
+        (MeshCorner::MeshCorner2, to),
+        (MeshCorner::MeshCorner3, to),
+    ] {
+        let (r, g, b, a) = color.to_tuple();
+        mesh.set_corner_color_rgba(corner, r, g, b, a);
+    }
+    mesh.end_patch();
+}
+
+/// Builds a mesh approximating a conic (sweep) gradient around `center`,
+/// out to `radius`, with stop offsets measured as fractions of a full turn
+/// clockwise from `start_angle`.
+#[rustler::nif]
+fn conic_gradient_new(
+    center: Point,
+    radius: f64,
+    start_angle: f64,
+    stops: Vec<ColorStop>,
+) -> Result<MeshArc, Error> {
+    let mesh = cairo::Mesh::new();
+    let stops = full_turn(stops);
+
+    for pair in stops.windows(2) {
+        let ((from_offset, from), (to_offset, to)) = (pair[0], pair[1]);
+        let sweep = (to_offset - from_offset) * 2.0 * PI;
+        if sweep <= 0.0 {
+            continue;
+        }
+
+        let start = start_angle + from_offset * 2.0 * PI;
+        let segments = (sweep / MAX_SWEEP).ceil() as usize;
+        for segment in 0..segments {
+            let (t0, t1) = (
+                segment as f64 / segments as f64,
+                (segment + 1) as f64 / segments as f64,
+            );
+            add_patch(
+                &mesh,
+                center,
+                radius,
+                (start + sweep * t0, mix(from, to, t0)),
+                (start + sweep * t1, mix(from, to, t1)),
+            );
+        }
+    }
+
+    Ok(ResourceArc::new(Guarded::new(XairoMesh::new(mesh))))
+}
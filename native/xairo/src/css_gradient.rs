@@ -0,0 +1,583 @@
+use crate::color::Rgba;
+use crate::error::Error;
+use crate::gradient::{self, ColorStop};
+use crate::linear_gradient::XairoLinearGradient;
+use crate::pattern::PatternArc;
+use crate::radial_gradient::XairoRadialGradient;
+use crate::resource::Guarded;
+use crate::shapes::Rectangle;
+use rustler::ResourceArc;
+use std::f64::consts::{PI, SQRT_2};
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::CssGradient(message.into())
+}
+
+/// A CSS `<length-percentage>`, limited to percentages and pixels.
+#[derive(Copy, Clone)]
+enum Length {
+    Percent(f64),
+    Px(f64),
+}
+
+impl Length {
+    fn resolve(self, total: f64) -> f64 {
+        match self {
+            Length::Percent(percent) => percent * total / 100.0,
+            Length::Px(px) => px,
+        }
+    }
+
+    /// The length as a fraction of `total`, e.g. of a gradient line.
+    fn fraction(self, total: f64) -> f64 {
+        match self {
+            Length::Percent(percent) => percent / 100.0,
+            Length::Px(px) if total > 0.0 => px / total,
+            Length::Px(_) => 0.0,
+        }
+    }
+}
+
+fn number(token: &str) -> Option<f64> {
+    token
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+fn length(token: &str) -> Option<Length> {
+    if let Some(percent) = token.strip_suffix('%') {
+        number(percent).map(Length::Percent)
+    } else if let Some(px) = token.strip_suffix("px") {
+        number(px).map(Length::Px)
+    } else {
+        number(token).filter(|n| *n == 0.0).map(Length::Px)
+    }
+}
+
+/// Parses a CSS `<angle>` into radians.
+fn angle(token: &str) -> Option<f64> {
+    if let Some(deg) = token.strip_suffix("deg") {
+        number(deg).map(f64::to_radians)
+    } else if let Some(grad) = token.strip_suffix("grad") {
+        number(grad).map(|grad| grad * PI / 200.0)
+    } else if let Some(rad) = token.strip_suffix("rad") {
+        number(rad)
+    } else if let Some(turn) = token.strip_suffix("turn") {
+        number(turn).map(|turn| turn * 2.0 * PI)
+    } else {
+        number(token).filter(|n| *n == 0.0)
+    }
+}
+
+/// Splits `input` on characters matching `separator`, ignoring any inside
+/// parentheses, and drops empty parts.
+fn split_top_level(input: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && separator(c) => {
+                parts.push(input[start..index].trim());
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(input[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+fn tokens(input: &str) -> Vec<&str> {
+    split_top_level(input, char::is_whitespace)
+}
+
+/// Splits a function call like `rgb(0, 0, 255)` into its name and arguments.
+fn function(input: &str) -> Option<(&str, &str)> {
+    let open = input.find('(')?;
+    let body = input.strip_suffix(')')?;
+    Some((input[..open].trim(), &body[open + 1..]))
+}
+
+fn color(token: &str) -> Option<Rgba> {
+    if let Some(hex) = token.strip_prefix('#') {
+        return hex_color(hex);
+    }
+    match function(token) {
+        Some(("rgb", args)) | Some(("rgba", args)) => rgb_color(args),
+        Some(("hsl", args)) | Some(("hsla", args)) => hsl_color(args),
+        Some(_) => None,
+        None => named_color(token),
+    }
+}
+
+fn hex_color(hex: &str) -> Option<Rgba> {
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|digit| digit as f64))
+        .collect::<Option<Vec<_>>>()?;
+    let channels: Vec<f64> = match digits.len() {
+        3 | 4 => digits.iter().map(|d| d * 17.0 / 255.0).collect(),
+        6 | 8 => digits
+            .chunks(2)
+            .map(|pair| (pair[0] * 16.0 + pair[1]) / 255.0)
+            .collect(),
+        _ => return None,
+    };
+    Some(Rgba {
+        red: channels[0],
+        green: channels[1],
+        blue: channels[2],
+        alpha: channels.get(3).copied().unwrap_or(1.0),
+    })
+}
+
+/// Splits the arguments of a color function, accepting both the legacy
+/// comma-separated syntax and the space-separated syntax with a `/` before
+/// the alpha.
+fn color_args(args: &str) -> Vec<&str> {
+    split_top_level(args, |c| c == ',' || c == '/' || c.is_whitespace())
+}
+
+fn alpha(token: Option<&&str>) -> Option<f64> {
+    match token {
+        None => Some(1.0),
+        Some(token) => match length(token) {
+            Some(Length::Percent(percent)) => Some(percent / 100.0),
+            _ => number(token),
+        },
+    }
+    .map(|alpha| alpha.clamp(0.0, 1.0))
+}
+
+fn rgb_color(args: &str) -> Option<Rgba> {
+    let args = color_args(args);
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let channel = |token: &str| {
+        match token.strip_suffix('%') {
+            Some(percent) => number(percent).map(|percent| percent / 100.0),
+            None => number(token).map(|value| value / 255.0),
+        }
+        .map(|value| value.clamp(0.0, 1.0))
+    };
+    Some(Rgba {
+        red: channel(args[0])?,
+        green: channel(args[1])?,
+        blue: channel(args[2])?,
+        alpha: alpha(args.get(3))?,
+    })
+}
+
+fn hsl_color(args: &str) -> Option<Rgba> {
+    let args = color_args(args);
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let hue = number(args[0]).or_else(|| angle(args[0]).map(f64::to_degrees))?;
+    let percent = |token: &str| {
+        let percent = number(token.strip_suffix('%')?)?;
+        Some((percent / 100.0).clamp(0.0, 1.0))
+    };
+    let (saturation, lightness) = (percent(args[1])?, percent(args[2])?);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (red, green, blue) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Some(Rgba {
+        red: red + m,
+        green: green + m,
+        blue: blue + m,
+        alpha: alpha(args.get(3))?,
+    })
+}
+
+fn named_color(name: &str) -> Option<Rgba> {
+    let (red, green, blue, alpha) = match name {
+        "transparent" => (0, 0, 0, 0.0),
+        "black" => (0, 0, 0, 1.0),
+        "silver" => (192, 192, 192, 1.0),
+        "gray" | "grey" => (128, 128, 128, 1.0),
+        "white" => (255, 255, 255, 1.0),
+        "maroon" => (128, 0, 0, 1.0),
+        "red" => (255, 0, 0, 1.0),
+        "purple" => (128, 0, 128, 1.0),
+        "fuchsia" | "magenta" => (255, 0, 255, 1.0),
+        "green" => (0, 128, 0, 1.0),
+        "lime" => (0, 255, 0, 1.0),
+        "olive" => (128, 128, 0, 1.0),
+        "yellow" => (255, 255, 0, 1.0),
+        "navy" => (0, 0, 128, 1.0),
+        "blue" => (0, 0, 255, 1.0),
+        "teal" => (0, 128, 128, 1.0),
+        "aqua" | "cyan" => (0, 255, 255, 1.0),
+        "orange" => (255, 165, 0, 1.0),
+        _ => return None,
+    };
+    Some(Rgba {
+        red: red as f64 / 255.0,
+        green: green as f64 / 255.0,
+        blue: blue as f64 / 255.0,
+        alpha,
+    })
+}
+
+/// Parses a `<color-stop>`, which may have zero, one or two positions.
+fn color_stop(arg: &str) -> Result<Vec<(Rgba, Option<Length>)>, Error> {
+    let tokens = tokens(arg);
+    let color = color(tokens[0]).ok_or_else(|| invalid(format!("invalid color stop {:?}", arg)))?;
+    let positions = tokens[1..]
+        .iter()
+        .map(|token| length(token))
+        .collect::<Option<Vec<_>>>()
+        .filter(|positions| positions.len() <= 2)
+        .ok_or_else(|| invalid(format!("invalid color stop {:?}", arg)))?;
+
+    if positions.is_empty() {
+        Ok(vec![(color, None)])
+    } else {
+        Ok(positions
+            .into_iter()
+            .map(|position| (color, Some(position)))
+            .collect())
+    }
+}
+
+/// Resolves the color stops' positions along a gradient line of the given
+/// length, following the CSS rules for missing and out-of-order positions.
+fn color_stops(args: &[&str], line_length: f64) -> Result<Vec<(f64, Rgba)>, Error> {
+    let mut stops = Vec::new();
+    for arg in args {
+        stops.extend(color_stop(arg)?);
+    }
+    if stops.is_empty() {
+        return Err(invalid("a gradient needs at least one color stop"));
+    }
+
+    let mut positions: Vec<Option<f64>> = stops
+        .iter()
+        .map(|(_, position)| position.map(|position| position.fraction(line_length)))
+        .collect();
+    let last = positions.len() - 1;
+    positions[0].get_or_insert(0.0);
+    positions[last].get_or_insert(1.0);
+
+    let mut max = f64::NEG_INFINITY;
+    for position in positions.iter_mut().flatten() {
+        max = max.max(*position);
+        *position = max;
+    }
+
+    let mut resolved = vec![0.0; positions.len()];
+    let mut previous = 0;
+    for (index, position) in positions.iter().enumerate() {
+        if let Some(position) = *position {
+            let start = resolved[previous];
+            let steps = (index - previous) as f64;
+            for (step, missing) in (previous + 1..index).enumerate() {
+                resolved[missing] = start + (position - start) * (step + 1) as f64 / steps;
+            }
+            resolved[index] = position;
+            previous = index;
+        }
+    }
+
+    Ok(resolved
+        .into_iter()
+        .zip(stops.into_iter().map(|(color, _)| color))
+        .collect())
+}
+
+/// Maps stop positions onto `[0, 1]` between the first and last stops,
+/// returning the positions of the first and last stops along the original
+/// line, so that the gradient's geometry can be adjusted to match.
+///
+/// This lets stops lie outside of the gradient line, and makes a repeating
+/// gradient repeat over the distance between its first and last stops.
+fn normalize(stops: Vec<(f64, Rgba)>) -> (f64, f64, Vec<ColorStop>) {
+    let first = stops[0].0;
+    let last = stops[stops.len() - 1].0;
+    if last <= first {
+        return (0.0, 1.0, stops);
+    }
+    let stops = stops
+        .into_iter()
+        .map(|(offset, color)| ((offset - first) / (last - first), color))
+        .collect();
+    (first, last, stops)
+}
+
+/// Parses a linear gradient's direction, either an angle or `to` followed by
+/// a side or corner, into a unit vector, or returns `None` if the argument is
+/// not a direction.
+fn direction(arg: &str, rect: &Rectangle) -> Result<Option<(f64, f64)>, Error> {
+    let tokens = tokens(arg);
+    if tokens[0] != "to" {
+        return Ok(angle(arg).map(|angle| (angle.sin(), -angle.cos())));
+    }
+    if tokens.len() == 1 {
+        return Err(invalid(format!("invalid direction {:?}", arg)));
+    }
+
+    let (mut x, mut y) = (0.0, 0.0);
+    for token in &tokens[1..] {
+        match *token {
+            "left" if x == 0.0 => x = -1.0,
+            "right" if x == 0.0 => x = 1.0,
+            "top" if y == 0.0 => y = -1.0,
+            "bottom" if y == 0.0 => y = 1.0,
+            _ => return Err(invalid(format!("invalid direction {:?}", arg))),
+        }
+    }
+
+    // Towards a corner, the gradient line is perpendicular to the diagonal
+    // joining the two neighbouring corners.
+    if x != 0.0 && y != 0.0 && rect.width > 0.0 && rect.height > 0.0 {
+        let (x, y) = (x * rect.height, y * rect.width);
+        let length = x.hypot(y);
+        return Ok(Some((x / length, y / length)));
+    }
+    let length = x.hypot(y);
+    Ok(Some((x / length, y / length)))
+}
+
+fn linear_gradient(args: &[&str], rect: &Rectangle) -> Result<cairo::LinearGradient, Error> {
+    let (direction, stop_args) = match direction(args[0], rect)? {
+        Some(direction) => (direction, &args[1..]),
+        None => ((0.0, 1.0), args),
+    };
+
+    let (dx, dy) = direction;
+    let line_length = (rect.width * dx).abs() + (rect.height * dy).abs();
+    let start_x = rect.corner.x + rect.width / 2.0 - dx * line_length / 2.0;
+    let start_y = rect.corner.y + rect.height / 2.0 - dy * line_length / 2.0;
+
+    let (first, last, stops) = normalize(color_stops(stop_args, line_length)?);
+    let gradient = cairo::LinearGradient::new(
+        start_x + dx * line_length * first,
+        start_y + dy * line_length * first,
+        start_x + dx * line_length * last,
+        start_y + dy * line_length * last,
+    );
+    gradient::add_color_stops(&gradient, &stops);
+    Ok(gradient)
+}
+
+enum Shape {
+    Circle,
+    Ellipse,
+}
+
+enum Size {
+    ClosestSide,
+    ClosestCorner,
+    FarthestSide,
+    FarthestCorner,
+    Explicit(Length, Option<Length>),
+}
+
+fn horizontal(token: &str) -> Option<Length> {
+    match token {
+        "left" => Some(Length::Percent(0.0)),
+        "center" => Some(Length::Percent(50.0)),
+        "right" => Some(Length::Percent(100.0)),
+        token => length(token),
+    }
+}
+
+fn vertical(token: &str) -> Option<Length> {
+    match token {
+        "top" => Some(Length::Percent(0.0)),
+        "center" => Some(Length::Percent(50.0)),
+        "bottom" => Some(Length::Percent(100.0)),
+        token => length(token),
+    }
+}
+
+/// Parses a `<position>` of one or two keywords or lengths into a point
+/// relative to the rectangle's corner.
+fn position(tokens: &[&str], rect: &Rectangle) -> Option<(f64, f64)> {
+    let (x, y) = match *tokens {
+        [token @ ("top" | "bottom")] => (Length::Percent(50.0), vertical(token)?),
+        [token] => (horizontal(token)?, Length::Percent(50.0)),
+        // Keywords may be given in either order, e.g. `top left`.
+        [first @ ("top" | "bottom"), second] | [second, first @ ("left" | "right")] => {
+            (horizontal(second)?, vertical(first)?)
+        }
+        [x, y] => (horizontal(x)?, vertical(y)?),
+        _ => return None,
+    };
+    Some((x.resolve(rect.width), y.resolve(rect.height)))
+}
+
+/// Parses a radial gradient's ending shape, size and position, returning the
+/// center and the horizontal and vertical radii.
+fn ending_shape(arg: &str, rect: &Rectangle) -> Result<((f64, f64), f64, f64), Error> {
+    let tokens = tokens(arg);
+    let error = || invalid(format!("invalid radial gradient shape {:?}", arg));
+
+    let at = tokens.iter().position(|token| *token == "at");
+    let (shape_tokens, position_tokens) = match at {
+        Some(at) => (&tokens[..at], &tokens[at + 1..]),
+        None => (&tokens[..], &[][..]),
+    };
+
+    let mut shape = None;
+    let mut size = None;
+    let mut lengths = Vec::new();
+    for token in shape_tokens {
+        match *token {
+            "circle" if shape.is_none() => shape = Some(Shape::Circle),
+            "ellipse" if shape.is_none() => shape = Some(Shape::Ellipse),
+            "closest-side" if size.is_none() => size = Some(Size::ClosestSide),
+            "closest-corner" if size.is_none() => size = Some(Size::ClosestCorner),
+            "farthest-side" if size.is_none() => size = Some(Size::FarthestSide),
+            "farthest-corner" if size.is_none() => size = Some(Size::FarthestCorner),
+            token => lengths.push(length(token).ok_or_else(error)?),
+        }
+    }
+    let size = match (size, lengths.as_slice()) {
+        (Some(size), []) => size,
+        (None, []) => Size::FarthestCorner,
+        (None, [radius]) => Size::Explicit(*radius, None),
+        (None, [rx, ry]) => Size::Explicit(*rx, Some(*ry)),
+        _ => return Err(error()),
+    };
+    let shape = match (shape, &size) {
+        (Some(shape), _) => shape,
+        (None, Size::Explicit(_, None)) => Shape::Circle,
+        (None, _) => Shape::Ellipse,
+    };
+
+    let (x, y) = if position_tokens.is_empty() {
+        (rect.width / 2.0, rect.height / 2.0)
+    } else {
+        position(position_tokens, rect).ok_or_else(error)?
+    };
+    let (left, right) = (x.abs(), (rect.width - x).abs());
+    let (top, bottom) = (y.abs(), (rect.height - y).abs());
+
+    let (rx, ry) = match (shape, size) {
+        (Shape::Circle, Size::Explicit(Length::Px(radius), None)) => (radius, radius),
+        (Shape::Circle, Size::Explicit(_, _)) => return Err(error()),
+        (Shape::Circle, size) => {
+            let sides = [left, right, top, bottom];
+            let corners = [
+                left.hypot(top),
+                right.hypot(top),
+                left.hypot(bottom),
+                right.hypot(bottom),
+            ];
+            let radius = match size {
+                Size::ClosestSide => sides.iter().copied().fold(f64::INFINITY, f64::min),
+                Size::FarthestSide => sides.iter().copied().fold(0.0, f64::max),
+                Size::ClosestCorner => corners.iter().copied().fold(f64::INFINITY, f64::min),
+                _ => corners.iter().copied().fold(0.0, f64::max),
+            };
+            (radius, radius)
+        }
+        (Shape::Ellipse, Size::Explicit(rx, Some(ry))) => {
+            (rx.resolve(rect.width), ry.resolve(rect.height))
+        }
+        (Shape::Ellipse, Size::Explicit(_, None)) => return Err(error()),
+        // The corner sizes keep the aspect ratio of the matching side sizes.
+        (Shape::Ellipse, Size::ClosestSide) => (left.min(right), top.min(bottom)),
+        (Shape::Ellipse, Size::FarthestSide) => (left.max(right), top.max(bottom)),
+        (Shape::Ellipse, Size::ClosestCorner) => {
+            (left.min(right) * SQRT_2, top.min(bottom) * SQRT_2)
+        }
+        (Shape::Ellipse, Size::FarthestCorner) => {
+            (left.max(right) * SQRT_2, top.max(bottom) * SQRT_2)
+        }
+    };
+    if rx < 0.0 || ry < 0.0 {
+        return Err(error());
+    }
+
+    Ok(((rect.corner.x + x, rect.corner.y + y), rx, ry))
+}
+
+fn radial_gradient(args: &[&str], rect: &Rectangle) -> Result<cairo::RadialGradient, Error> {
+    let (shape, stop_args) = match color(tokens(args[0])[0]) {
+        Some(_) => ("", args),
+        None => (args[0], &args[1..]),
+    };
+    let ((cx, cy), rx, ry) = ending_shape(shape, rect)?;
+
+    // cairo's radial gradients are circular, so an ellipse is drawn as a
+    // circle of radius `rx` centered on the origin, scaled vertically by the
+    // pattern matrix.
+    let ellipse = rx > 0.0 && ry > 0.0 && (rx - ry).abs() > f64::EPSILON;
+    let radius = if ellipse { rx } else { rx.max(ry) };
+    let (first, last, stops) = normalize(
+        color_stops(stop_args, radius)?
+            .into_iter()
+            .map(|(offset, color)| (offset.max(0.0), color))
+            .collect(),
+    );
+
+    let gradient = if ellipse {
+        let gradient =
+            cairo::RadialGradient::new(0.0, 0.0, radius * first, 0.0, 0.0, radius * last);
+        let scale = rx / ry;
+        gradient.set_matrix(cairo::Matrix::new(1.0, 0.0, 0.0, scale, -cx, -cy * scale));
+        gradient
+    } else {
+        cairo::RadialGradient::new(cx, cy, radius * first, cx, cy, radius * last)
+    };
+    gradient::add_color_stops(&gradient, &stops);
+    Ok(gradient)
+}
+
+/// Builds a linear or radial gradient from a CSS `linear-gradient()`,
+/// `radial-gradient()` or repeating gradient function, sized to fill `rect`.
+#[rustler::nif]
+fn css_gradient(css: String, rect: Rectangle) -> Result<PatternArc, Error> {
+    let css = css.trim().to_ascii_lowercase();
+    let (name, body) = function(&css).ok_or_else(|| invalid("expected a gradient function"))?;
+    let args = split_top_level(body, |c| c == ',');
+    if args.is_empty() {
+        return Err(invalid("a gradient needs at least one color stop"));
+    }
+
+    let (repeating, name) = match name.strip_prefix("repeating-") {
+        Some(name) => (true, name),
+        None => (false, name),
+    };
+    let extend = if repeating {
+        cairo::Extend::Repeat
+    } else {
+        cairo::Extend::Pad
+    };
+
+    match name {
+        "linear-gradient" => {
+            let gradient = linear_gradient(&args, &rect)?;
+            gradient.set_extend(extend);
+            Ok(PatternArc::LinearGradient(ResourceArc::new(Guarded::new(
+                XairoLinearGradient { gradient },
+            ))))
+        }
+        "radial-gradient" => {
+            let gradient = radial_gradient(&args, &rect)?;
+            gradient.set_extend(extend);
+            Ok(PatternArc::RadialGradient(ResourceArc::new(Guarded::new(
+                XairoRadialGradient { gradient },
+            ))))
+        }
+        _ => Err(invalid(format!("unsupported gradient function {:?}", name))),
+    }
+}
@@ -1,101 +1,189 @@
+use crate::error::Error;
+use crate::packed;
 use crate::shapes::{Arc, Curve, Point, Rectangle, Vector};
-use crate::xairo_image::ImageArc;
+use crate::status;
+use crate::xairo_image::{ImageArc, ImageResult};
+use rustler::Binary;
 
 #[rustler::nif]
-fn move_to(image: ImageArc, point: Point) -> ImageArc {
-    image.context.move_to(point.x, point.y);
-    image
+fn move_to(image: ImageArc, point: Point) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.move_to(point.x, point.y);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn rel_move_to(image: ImageArc, vector: Vector) -> ImageArc {
-    image.context.rel_move_to(vector.x, vector.y);
-    image
+fn rel_move_to(image: ImageArc, vector: Vector) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.rel_move_to(vector.x, vector.y);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn line_to(image: ImageArc, point: Point) -> ImageArc {
-    image.context.line_to(point.x, point.y);
-    image
+fn line_to(image: ImageArc, point: Point) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.line_to(point.x, point.y);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn rel_line_to(image: ImageArc, vector: Vector) -> ImageArc {
-    image.context.rel_line_to(vector.x, vector.y);
-    image
+fn rel_line_to(image: ImageArc, vector: Vector) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.rel_line_to(vector.x, vector.y);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn close_path(image: ImageArc) -> ImageArc {
-    image.context.close_path();
-    image
+fn close_path(image: ImageArc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.close_path();
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn arc(image: ImageArc, shape: Arc) -> ImageArc {
-    image.context.arc(
-        shape.center.x,
-        shape.center.y,
-        shape.radius,
-        shape.start_angle,
-        shape.stop_angle,
-    );
-    image
+fn arc(image: ImageArc, shape: Arc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.arc(
+            shape.center.x,
+            shape.center.y,
+            shape.radius,
+            shape.start_angle,
+            shape.stop_angle,
+        );
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn arc_negative(image: ImageArc, shape: Arc) -> ImageArc {
-    image.context.arc_negative(
-        shape.center.x,
-        shape.center.y,
-        shape.radius,
-        shape.start_angle,
-        shape.stop_angle,
-    );
-    image
+fn arc_negative(image: ImageArc, shape: Arc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.arc_negative(
+            shape.center.x,
+            shape.center.y,
+            shape.radius,
+            shape.start_angle,
+            shape.stop_angle,
+        );
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn rectangle(image: ImageArc, rect: Rectangle) -> ImageArc {
-    image
-        .context
-        .rectangle(rect.corner.x, rect.corner.y, rect.width, rect.height);
-    image
+fn rectangle(image: ImageArc, rect: Rectangle) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard
+            .context
+            .rectangle(rect.corner.x, rect.corner.y, rect.width, rect.height);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn curve_to(image: ImageArc, curve: Curve) -> ImageArc {
+fn curve_to(image: ImageArc, curve: Curve) -> ImageResult {
     let ((x1, y1), (x2, y2), (x3, y3)) = curve.to_tuple();
-    image.context.curve_to(x1, y1, x2, y2, x3, y3);
-    image
+    {
+        let guard = image.lock()?;
+        guard.context.curve_to(x1, y1, x2, y2, x3, y3);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn rel_curve_to(image: ImageArc, curve: Curve) -> ImageArc {
+fn rel_curve_to(image: ImageArc, curve: Curve) -> ImageResult {
     let ((x1, y1), (x2, y2), (x3, y3)) = curve.to_tuple();
-    image.context.rel_curve_to(x1, y1, x2, y2, x3, y3);
-    image
+    {
+        let guard = image.lock()?;
+        guard.context.rel_curve_to(x1, y1, x2, y2, x3, y3);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn new_path(image: ImageArc) -> ImageArc {
-    image.context.new_path();
-    image
+fn new_path(image: ImageArc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.new_path();
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn new_sub_path(image: ImageArc) -> ImageArc {
-    image.context.new_sub_path();
-    image
+fn new_sub_path(image: ImageArc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.new_sub_path();
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn current_point(image: ImageArc) -> Result<(f64, f64), ()> {
-    match image.context.has_current_point() {
-        Ok(true) => match image.context.current_point() {
+fn polyline<'a>(image: ImageArc, points: Binary<'a>) -> ImageResult {
+    let points = packed::decode_points(points.as_slice())?;
+    {
+        let guard = image.lock()?;
+        add_polyline(&guard.context, &points);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
+}
+
+#[rustler::nif]
+fn polygon<'a>(image: ImageArc, points: Binary<'a>) -> ImageResult {
+    let points = packed::decode_points(points.as_slice())?;
+    {
+        let guard = image.lock()?;
+        add_polyline(&guard.context, &points);
+        if !points.is_empty() {
+            guard.context.close_path();
+        }
+        status::check(&guard.context)?;
+    }
+    Ok(image)
+}
+
+#[rustler::nif]
+fn current_point(image: ImageArc) -> Result<(f64, f64), Error> {
+    let guard = image.lock()?;
+    match guard.context.has_current_point() {
+        Ok(true) => match guard.context.current_point() {
             Ok((x, y)) => Ok((x, y)),
-            Err(_) => Err(()),
+            Err(_) => Err(Error::NoCurrentPoint),
         },
-        _ => Err(()),
+        _ => Err(Error::NoCurrentPoint),
+    }
+}
+
+/// Moves to the first point and draws straight lines through the rest.
+fn add_polyline(context: &cairo::Context, points: &[(f64, f64)]) {
+    if let Some(((x, y), rest)) = points.split_first() {
+        context.move_to(*x, *y);
+        for (x, y) in rest {
+            context.line_to(*x, *y);
+        }
     }
 }
@@ -1,6 +1,62 @@
-use rustler::{Env, Term};
+use crate::status::Status;
+use rustler::{Atom, Encoder, Env, Term};
 use thiserror::Error as ThisErrorError;
 
+mod atoms {
+    rustler::atoms! {
+        context_create,
+        surface_create,
+        file_create,
+        file_write,
+        path_render,
+        set_source,
+        text_extents,
+        translated_vector,
+        translated_point,
+        file_type_mismatch,
+        uninvertible_matrix,
+        copy_path,
+        surface_source,
+        mask,
+        control_point,
+        corner_color,
+        patch_count,
+        mesh_path,
+        color_stop_count,
+        color_stop,
+        linear_points,
+        radial_circles,
+        solid_pattern_color,
+        font_load,
+        text_shaping,
+        invalid_script,
+        invalid_language,
+        text_along_path,
+        font_options,
+        font_face_create,
+        extents,
+        no_current_point,
+        invalid_status,
+        resource_finished,
+        resource_lock,
+        resource_busy,
+        same_resource,
+        context_state,
+        draw_command,
+        invalid_draw_command,
+        point_data,
+        binary_alloc,
+        pixel_access,
+        unsupported_format,
+        pixel_out_of_bounds,
+        pixel_data,
+        css_gradient,
+        mesh_corner,
+        mesh_construction,
+        mesh_patch,
+    }
+}
+
 #[derive(Debug, ThisErrorError)]
 pub enum Error {
     #[error("Error creating image context")]
@@ -49,10 +105,152 @@ pub enum Error {
     RadialCircles,
     #[error("Error retrieving color for solid pattern")]
     SolidPatternColor,
+    #[error("Error loading font from {0}")]
+    FontLoad(String),
+    #[error("Error shaping text")]
+    TextShaping,
+    #[error("Invalid script {0}")]
+    InvalidScript(String),
+    #[error("Invalid language {0}")]
+    InvalidLanguage(String),
+    #[error("Error laying out text along path")]
+    TextAlongPath,
+    #[error("Error accessing font options")]
+    FontOptions,
+    #[error("Error creating font face")]
+    FontFaceCreate,
+    #[error("Error calculating {0} extents")]
+    Extents(&'static str),
+    #[error("No current point set")]
+    NoCurrentPoint,
+    #[error("Image context is in an error state: {0:?}")]
+    InvalidStatus(Status),
+    #[error("Resource used after being finished")]
+    ResourceFinished,
+    #[error("Error acquiring resource lock")]
+    ResourceLock,
+    #[error("Resource is locked by another process")]
+    ResourceBusy,
+    #[error("Resource cannot be used with itself")]
+    SameResource,
+    #[error("Error calling {0} on image context")]
+    ContextState(&'static str),
+    #[error("Error running draw command at index {0}: {1}")]
+    DrawCommand(usize, Box<Error>),
+    #[error("Invalid draw command")]
+    InvalidDrawCommand,
+    #[error("Packed point data of {0} bytes is not a list of f64 x/y pairs")]
+    PointData(usize),
+    #[error("Error allocating binary")]
+    BinaryAlloc,
+    #[error("Error accessing image surface pixel data")]
+    PixelAccess,
+    #[error("Unsupported image surface format")]
+    UnsupportedFormat,
+    #[error("Pixel ({0}, {1}) is outside the image surface")]
+    PixelOutOfBounds(i32, i32),
+    #[error("Pixel data of {0} bytes does not match the image surface size of {1} bytes")]
+    PixelData(usize, usize),
+    #[error("Invalid CSS gradient: {0}")]
+    CssGradient(String),
+    #[error("Invalid mesh corner {0}, expected 0 to 3")]
+    MeshCorner(usize),
+    #[error("Invalid mesh construction: {0}")]
+    MeshConstruction(&'static str),
+    #[error("Error building mesh patch at index {0}: {1}")]
+    MeshPatch(usize, Box<Error>),
+}
+
+impl Error {
+    fn reason(&self) -> Atom {
+        match self {
+            Error::ContextCreate => atoms::context_create(),
+            Error::SurfaceCreate => atoms::surface_create(),
+            Error::FileCreate(_) => atoms::file_create(),
+            Error::FileWrite(_) => atoms::file_write(),
+            Error::PathRender(_) => atoms::path_render(),
+            Error::SetSource(_) => atoms::set_source(),
+            Error::TextExtents => atoms::text_extents(),
+            Error::TranslatedVector => atoms::translated_vector(),
+            Error::TranslatedPoint => atoms::translated_point(),
+            Error::FileTypeMismatch => atoms::file_type_mismatch(),
+            Error::UninvertibleMatrix => atoms::uninvertible_matrix(),
+            Error::CopyPath => atoms::copy_path(),
+            Error::SurfaceSource => atoms::surface_source(),
+            Error::MaskError => atoms::mask(),
+            Error::ControlPointError(_, _) => atoms::control_point(),
+            Error::CornerColorError(_, _) => atoms::corner_color(),
+            Error::PatchCount => atoms::patch_count(),
+            Error::MeshPath(_) => atoms::mesh_path(),
+            Error::ColorStopCount => atoms::color_stop_count(),
+            Error::ColorStop(_) => atoms::color_stop(),
+            Error::LinearPoints => atoms::linear_points(),
+            Error::RadialCircles => atoms::radial_circles(),
+            Error::SolidPatternColor => atoms::solid_pattern_color(),
+            Error::FontLoad(_) => atoms::font_load(),
+            Error::TextShaping => atoms::text_shaping(),
+            Error::InvalidScript(_) => atoms::invalid_script(),
+            Error::InvalidLanguage(_) => atoms::invalid_language(),
+            Error::TextAlongPath => atoms::text_along_path(),
+            Error::FontOptions => atoms::font_options(),
+            Error::FontFaceCreate => atoms::font_face_create(),
+            Error::Extents(_) => atoms::extents(),
+            Error::NoCurrentPoint => atoms::no_current_point(),
+            Error::InvalidStatus(_) => atoms::invalid_status(),
+            Error::ResourceFinished => atoms::resource_finished(),
+            Error::ResourceLock => atoms::resource_lock(),
+            Error::ResourceBusy => atoms::resource_busy(),
+            Error::SameResource => atoms::same_resource(),
+            Error::ContextState(_) => atoms::context_state(),
+            Error::DrawCommand(_, _) => atoms::draw_command(),
+            Error::InvalidDrawCommand => atoms::invalid_draw_command(),
+            Error::PointData(_) => atoms::point_data(),
+            Error::BinaryAlloc => atoms::binary_alloc(),
+            Error::PixelAccess => atoms::pixel_access(),
+            Error::UnsupportedFormat => atoms::unsupported_format(),
+            Error::PixelOutOfBounds(_, _) => atoms::pixel_out_of_bounds(),
+            Error::PixelData(_, _) => atoms::pixel_data(),
+            Error::CssGradient(_) => atoms::css_gradient(),
+            Error::MeshCorner(_) => atoms::mesh_corner(),
+            Error::MeshConstruction(_) => atoms::mesh_construction(),
+            Error::MeshPatch(_, _) => atoms::mesh_patch(),
+        }
+    }
+
+    fn details<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            Error::FileCreate(detail)
+            | Error::FileWrite(detail)
+            | Error::FontLoad(detail)
+            | Error::InvalidScript(detail)
+            | Error::InvalidLanguage(detail)
+            | Error::CssGradient(detail) => detail.encode(env),
+            Error::PathRender(detail)
+            | Error::SetSource(detail)
+            | Error::Extents(detail)
+            | Error::ContextState(detail)
+            | Error::MeshConstruction(detail) => detail.encode(env),
+            Error::ControlPointError(corner, patch) | Error::CornerColorError(corner, patch) => {
+                (corner, patch).encode(env)
+            }
+            Error::MeshPath(index) | Error::MeshCorner(index) => index.encode(env),
+            Error::ColorStop(index) => index.encode(env),
+            Error::PointData(size) => size.encode(env),
+            Error::PixelOutOfBounds(x, y) => (x, y).encode(env),
+            Error::PixelData(size, expected) => (size, expected).encode(env),
+            Error::InvalidStatus(status) => status.encode(env),
+            Error::DrawCommand(index, error) | Error::MeshPatch(index, error) => {
+                (index, error.encode(env)).encode(env)
+            }
+            _ => rustler::types::atom::nil().encode(env),
+        }
+    }
 }
 
-impl rustler::Encoder for Error {
+/// Errors are encoded as a `{reason, details}` tuple, where `reason` is an atom
+/// identifying the failure and `details` holds any data attached to it, or `nil`.
+impl Encoder for Error {
     fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
-        format!("{}", self).encode(env)
+        (self.reason(), self.details(env)).encode(env)
     }
 }
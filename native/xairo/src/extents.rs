@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::shapes::Point;
 use crate::xairo_image::ImageArc;
 
@@ -12,14 +13,24 @@ pub struct Extents {
 }
 
 #[rustler::nif]
-fn extents(image: ImageArc) -> Result<Extents, ()> {
-    let (fx1, fy1, fx2, fy2) = image.context.fill_extents().unwrap();
-    let (sx1, sy1, sx2, sy2) = image.context.stroke_extents().unwrap();
-    let (px1, py1, px2, py2) = image.context.path_extents().unwrap();
+fn extents(image: ImageArc) -> Result<Extents, Error> {
+    let guard = image.lock()?;
+    let fill = match guard.context.fill_extents() {
+        Ok(extents) => extent_tuple(extents),
+        Err(_) => return Err(Error::Extents("fill")),
+    };
+    let stroke = match guard.context.stroke_extents() {
+        Ok(extents) => extent_tuple(extents),
+        Err(_) => return Err(Error::Extents("stroke")),
+    };
+    let path = match guard.context.path_extents() {
+        Ok(extents) => extent_tuple(extents),
+        Err(_) => return Err(Error::Extents("path")),
+    };
 
-    Ok(Extents {
-        fill: (Point { x: fx1, y: fy1 }, Point { x: fx2, y: fy2 }),
-        stroke: (Point { x: sx1, y: sy1 }, Point { x: sx2, y: sy2 }),
-        path: (Point { x: px1, y: py1 }, Point { x: px2, y: py2 }),
-    })
+    Ok(Extents { fill, stroke, path })
+}
+
+fn extent_tuple((x1, y1, x2, y2): (f64, f64, f64, f64)) -> ExtentTuple {
+    (Point { x: x1, y: y1 }, Point { x: x2, y: y2 })
 }
@@ -0,0 +1,155 @@
+use crate::error::Error;
+use crate::xairo_image::{ImageArc, ImageResult};
+
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum Antialias {
+    Default,
+    None,
+    Gray,
+    Subpixel,
+    Fast,
+    Good,
+    Best,
+}
+
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum HintStyle {
+    Default,
+    None,
+    Slight,
+    Medium,
+    Full,
+}
+
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum HintMetrics {
+    Default,
+    Off,
+    On,
+}
+
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum SubpixelOrder {
+    Default,
+    Rgb,
+    Bgr,
+    Vrgb,
+    Vbgr,
+}
+
+#[derive(Copy, Clone, Debug, NifStruct)]
+#[module = "Xairo.Text.FontOptions"]
+pub struct FontOptions {
+    pub antialias: Antialias,
+    pub hint_style: HintStyle,
+    pub hint_metrics: HintMetrics,
+    pub subpixel_order: SubpixelOrder,
+}
+
+#[rustler::nif]
+fn set_font_options(image: ImageArc, options: FontOptions) -> ImageResult {
+    match cairo::FontOptions::new() {
+        Ok(mut cairo_options) => {
+            cairo_options.set_antialias(match_antialias(options.antialias));
+            cairo_options.set_hint_style(match_hint_style(options.hint_style));
+            cairo_options.set_hint_metrics(match_hint_metrics(options.hint_metrics));
+            cairo_options.set_subpixel_order(match_subpixel_order(options.subpixel_order));
+            image.lock()?.context.set_font_options(&cairo_options);
+            Ok(image)
+        }
+        Err(_) => Err(Error::FontOptions),
+    }
+}
+
+#[rustler::nif]
+fn font_options(image: ImageArc) -> Result<FontOptions, Error> {
+    match image.lock()?.context.font_options() {
+        Ok(font_options) => Ok(FontOptions {
+            antialias: antialias_from_cairo(font_options.antialias()),
+            hint_style: hint_style_from_cairo(font_options.hint_style()),
+            hint_metrics: hint_metrics_from_cairo(font_options.hint_metrics()),
+            subpixel_order: subpixel_order_from_cairo(font_options.subpixel_order()),
+        }),
+        Err(_) => Err(Error::FontOptions),
+    }
+}
+
+fn match_antialias(antialias: Antialias) -> cairo::Antialias {
+    match antialias {
+        Antialias::Default => cairo::Antialias::Default,
+        Antialias::None => cairo::Antialias::None,
+        Antialias::Gray => cairo::Antialias::Gray,
+        Antialias::Subpixel => cairo::Antialias::Subpixel,
+        Antialias::Fast => cairo::Antialias::Fast,
+        Antialias::Good => cairo::Antialias::Good,
+        Antialias::Best => cairo::Antialias::Best,
+    }
+}
+
+fn match_hint_style(hint_style: HintStyle) -> cairo::HintStyle {
+    match hint_style {
+        HintStyle::Default => cairo::HintStyle::Default,
+        HintStyle::None => cairo::HintStyle::None,
+        HintStyle::Slight => cairo::HintStyle::Slight,
+        HintStyle::Medium => cairo::HintStyle::Medium,
+        HintStyle::Full => cairo::HintStyle::Full,
+    }
+}
+
+fn match_hint_metrics(hint_metrics: HintMetrics) -> cairo::HintMetrics {
+    match hint_metrics {
+        HintMetrics::Default => cairo::HintMetrics::Default,
+        HintMetrics::Off => cairo::HintMetrics::Off,
+        HintMetrics::On => cairo::HintMetrics::On,
+    }
+}
+
+fn match_subpixel_order(subpixel_order: SubpixelOrder) -> cairo::SubpixelOrder {
+    match subpixel_order {
+        SubpixelOrder::Default => cairo::SubpixelOrder::Default,
+        SubpixelOrder::Rgb => cairo::SubpixelOrder::Rgb,
+        SubpixelOrder::Bgr => cairo::SubpixelOrder::Bgr,
+        SubpixelOrder::Vrgb => cairo::SubpixelOrder::Vrgb,
+        SubpixelOrder::Vbgr => cairo::SubpixelOrder::Vbgr,
+    }
+}
+
+fn antialias_from_cairo(antialias: cairo::Antialias) -> Antialias {
+    match antialias {
+        cairo::Antialias::None => Antialias::None,
+        cairo::Antialias::Gray => Antialias::Gray,
+        cairo::Antialias::Subpixel => Antialias::Subpixel,
+        cairo::Antialias::Fast => Antialias::Fast,
+        cairo::Antialias::Good => Antialias::Good,
+        cairo::Antialias::Best => Antialias::Best,
+        _ => Antialias::Default,
+    }
+}
+
+fn hint_style_from_cairo(hint_style: cairo::HintStyle) -> HintStyle {
+    match hint_style {
+        cairo::HintStyle::None => HintStyle::None,
+        cairo::HintStyle::Slight => HintStyle::Slight,
+        cairo::HintStyle::Medium => HintStyle::Medium,
+        cairo::HintStyle::Full => HintStyle::Full,
+        _ => HintStyle::Default,
+    }
+}
+
+fn hint_metrics_from_cairo(hint_metrics: cairo::HintMetrics) -> HintMetrics {
+    match hint_metrics {
+        cairo::HintMetrics::Off => HintMetrics::Off,
+        cairo::HintMetrics::On => HintMetrics::On,
+        _ => HintMetrics::Default,
+    }
+}
+
+fn subpixel_order_from_cairo(subpixel_order: cairo::SubpixelOrder) -> SubpixelOrder {
+    match subpixel_order {
+        cairo::SubpixelOrder::Rgb => SubpixelOrder::Rgb,
+        cairo::SubpixelOrder::Bgr => SubpixelOrder::Bgr,
+        cairo::SubpixelOrder::Vrgb => SubpixelOrder::Vrgb,
+        cairo::SubpixelOrder::Vbgr => SubpixelOrder::Vbgr,
+        _ => SubpixelOrder::Default,
+    }
+}
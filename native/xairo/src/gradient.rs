@@ -0,0 +1,50 @@
+use crate::color::Rgba;
+use crate::error::Error;
+
+/// A color stop of a linear or radial gradient, as `{offset, color}`.
+pub type ColorStop = (f64, Rgba);
+
+pub fn color_stop(gradient: &cairo::Gradient, index: isize) -> Result<ColorStop, Error> {
+    match gradient.color_stop_rgba(index) {
+        Ok((offset, red, green, blue, alpha)) => Ok((
+            offset,
+            Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            },
+        )),
+        Err(_) => Err(Error::ColorStop(index)),
+    }
+}
+
+pub fn color_stops(gradient: &cairo::Gradient) -> Result<Vec<ColorStop>, Error> {
+    let count = match gradient.color_stop_count() {
+        Ok(count) => count,
+        Err(_) => return Err(Error::ColorStopCount),
+    };
+    (0..count)
+        .map(|index| color_stop(gradient, index))
+        .collect()
+}
+
+/// Sets up `to` as a copy of `from` with the given color stops in place of
+/// its own.
+///
+/// cairo only allows appending color stops to a gradient, so replacing them
+/// means building a new gradient with the same geometry, matrix, extend and
+/// filter.
+pub fn copy_with_color_stops(from: &cairo::Gradient, to: &cairo::Gradient, stops: &[ColorStop]) {
+    to.set_matrix(from.matrix());
+    to.set_extend(from.extend());
+    to.set_filter(from.filter());
+    add_color_stops(to, stops);
+}
+
+pub fn add_color_stops(gradient: &cairo::Gradient, stops: &[ColorStop]) {
+    for (offset, color) in stops {
+        let (r, g, b, a) = color.to_tuple();
+        gradient.add_color_stop_rgba(*offset, r, g, b, a);
+    }
+}
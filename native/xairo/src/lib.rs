@@ -3,23 +3,44 @@ extern crate rustler_codegen;
 use rustler::{Env, Term};
 
 mod color;
+mod commands;
+mod conic_gradient;
+mod css_gradient;
 mod drawing;
 mod error;
 mod extents;
+mod font_options;
+mod gradient;
 mod line_cap;
 mod line_join;
 mod linear_gradient;
 use linear_gradient::XairoLinearGradient;
 mod matrix;
 mod mesh;
+mod mesh_patch;
+mod packed;
 use mesh::XairoMesh;
 mod path;
 use path::XairoPath;
+mod pattern;
+mod pixels;
 mod radial_gradient;
 use radial_gradient::XairoRadialGradient;
+mod raster_pattern;
+use raster_pattern::XairoRasterPattern;
+mod recording;
+mod resource;
+use resource::Guarded;
+mod schedule;
+use schedule::Yielding;
 mod shapes;
+mod shaping;
+use shaping::XairoFontFace;
 mod solid_pattern;
 use solid_pattern::XairoSolidPattern;
+mod status;
+mod surface_pattern;
+use surface_pattern::XairoSurfacePattern;
 mod text;
 mod transformations;
 mod xairo_image;
@@ -33,88 +54,169 @@ rustler::init!(
         xairo_image::new_svg_image,
         xairo_image::new_pdf_image,
         xairo_image::new_ps_image,
+        Yielding::<xairo_image::create_for_rectangle>,
+        Yielding::<xairo_image::create_similar>,
+        Yielding::<xairo_image::create_similar_image>,
         xairo_image::save_image,
-        xairo_image::set_document_unit,
+        Yielding::<xairo_image::image_format>,
+        Yielding::<xairo_image::image_info>,
+        Yielding::<xairo_image::set_document_unit>,
+        Yielding::<xairo_image::set_device_scale>,
+        Yielding::<xairo_image::device_scale>,
+        Yielding::<xairo_image::set_device_offset>,
+        Yielding::<xairo_image::device_offset>,
+        Yielding::<xairo_image::set_fallback_resolution>,
+        Yielding::<xairo_image::fallback_resolution>,
         xairo_image::mask_surface,
-        xairo_image::set_surface_pattern_source,
+        // recording
+        xairo_image::new_recording,
+        recording::replay,
+        Yielding::<recording::ink_extents>,
+        // status
+        Yielding::<status::status>,
+        // pixels
+        pixels::image_data,
+        Yielding::<pixels::get_pixel>,
+        pixels::set_image_data,
         // extents
-        extents::extents,
+        Yielding::<extents::extents>,
+        // commands
+        commands::draw_commands,
         // drawing
-        drawing::move_to,
-        drawing::rel_move_to,
-        drawing::line_to,
-        drawing::rel_line_to,
-        drawing::close_path,
-        drawing::arc,
-        drawing::arc_negative,
-        drawing::rectangle,
-        drawing::curve_to,
-        drawing::rel_curve_to,
-        drawing::new_path,
-        drawing::new_sub_path,
-        drawing::current_point,
+        Yielding::<drawing::move_to>,
+        Yielding::<drawing::rel_move_to>,
+        Yielding::<drawing::line_to>,
+        Yielding::<drawing::rel_line_to>,
+        Yielding::<drawing::close_path>,
+        Yielding::<drawing::arc>,
+        Yielding::<drawing::arc_negative>,
+        Yielding::<drawing::rectangle>,
+        Yielding::<drawing::curve_to>,
+        Yielding::<drawing::rel_curve_to>,
+        Yielding::<drawing::new_path>,
+        Yielding::<drawing::new_sub_path>,
+        Yielding::<drawing::current_point>,
+        Yielding::<drawing::polyline>,
+        Yielding::<drawing::polygon>,
         // color
-        color::set_color,
+        Yielding::<color::set_color>,
         color::stroke,
         color::fill,
         color::paint,
         // shapes
-        shapes::set_dash,
-        shapes::set_line_width,
+        Yielding::<shapes::set_dash>,
+        Yielding::<shapes::set_line_width>,
         // line cap
-        line_cap::set_line_cap,
+        Yielding::<line_cap::set_line_cap>,
         // line join
-        line_join::set_line_join,
+        Yielding::<line_join::set_line_join>,
+        // pattern
+        pattern::pattern_type,
+        Yielding::<pattern::set_source>,
+        pattern::mask,
+        // css gradient
+        css_gradient::css_gradient,
         // linear gradient
-        linear_gradient::set_linear_gradient_source,
-        linear_gradient::set_linear_gradient_mask,
         linear_gradient::linear_gradient_new,
-        linear_gradient::linear_gradient_add_color_stop,
-        linear_gradient::linear_gradient_color_stop_count,
-        linear_gradient::linear_gradient_color_stop,
-        linear_gradient::linear_gradient_linear_points,
+        Yielding::<linear_gradient::linear_gradient_add_color_stop>,
+        Yielding::<linear_gradient::linear_gradient_color_stop_count>,
+        Yielding::<linear_gradient::linear_gradient_color_stop>,
+        Yielding::<linear_gradient::linear_gradient_color_stops>,
+        Yielding::<linear_gradient::linear_gradient_set_color_stops>,
+        Yielding::<linear_gradient::linear_gradient_linear_points>,
+        Yielding::<linear_gradient::linear_gradient_set_matrix>,
+        Yielding::<linear_gradient::linear_gradient_matrix>,
+        Yielding::<linear_gradient::linear_gradient_set_extend>,
+        Yielding::<linear_gradient::linear_gradient_extend>,
+        Yielding::<linear_gradient::linear_gradient_set_filter>,
+        Yielding::<linear_gradient::linear_gradient_filter>,
         // radial gradient
-        radial_gradient::set_radial_gradient_source,
-        radial_gradient::set_radial_gradient_mask,
         radial_gradient::radial_gradient_new,
-        radial_gradient::radial_gradient_add_color_stop,
-        radial_gradient::radial_gradient_color_stop_count,
-        radial_gradient::radial_gradient_color_stop,
-        radial_gradient::radial_gradient_radial_circles,
+        Yielding::<radial_gradient::radial_gradient_add_color_stop>,
+        Yielding::<radial_gradient::radial_gradient_color_stop_count>,
+        Yielding::<radial_gradient::radial_gradient_color_stop>,
+        Yielding::<radial_gradient::radial_gradient_color_stops>,
+        Yielding::<radial_gradient::radial_gradient_set_color_stops>,
+        Yielding::<radial_gradient::radial_gradient_radial_circles>,
+        Yielding::<radial_gradient::radial_gradient_set_matrix>,
+        Yielding::<radial_gradient::radial_gradient_matrix>,
+        Yielding::<radial_gradient::radial_gradient_set_extend>,
+        Yielding::<radial_gradient::radial_gradient_extend>,
+        Yielding::<radial_gradient::radial_gradient_set_filter>,
+        Yielding::<radial_gradient::radial_gradient_filter>,
         // mesh
-        mesh::set_mesh_source,
-        mesh::set_mesh_mask,
         mesh::mesh_new,
-        mesh::mesh_begin_patch,
-        mesh::mesh_end_patch,
-        mesh::mesh_move_to,
-        mesh::mesh_line_to,
-        mesh::mesh_curve_to,
-        mesh::mesh_set_control_point,
-        mesh::mesh_set_corner_color,
-        mesh::mesh_control_point,
-        mesh::mesh_corner_color,
-        mesh::mesh_patch_count,
-        mesh::mesh_path,
+        Yielding::<mesh::mesh_begin_patch>,
+        Yielding::<mesh::mesh_end_patch>,
+        Yielding::<mesh::mesh_move_to>,
+        Yielding::<mesh::mesh_line_to>,
+        Yielding::<mesh::mesh_curve_to>,
+        Yielding::<mesh::mesh_set_control_point>,
+        Yielding::<mesh::mesh_set_corner_color>,
+        Yielding::<mesh::mesh_control_point>,
+        Yielding::<mesh::mesh_corner_color>,
+        Yielding::<mesh::mesh_patch_count>,
+        Yielding::<mesh::mesh_path>,
+        Yielding::<mesh::mesh_set_matrix>,
+        Yielding::<mesh::mesh_matrix>,
+        Yielding::<mesh::mesh_set_extend>,
+        Yielding::<mesh::mesh_extend>,
+        Yielding::<mesh::mesh_set_filter>,
+        Yielding::<mesh::mesh_filter>,
+        mesh_patch::mesh_from_patches,
+        Yielding::<mesh_patch::mesh_patches>,
+        // conic gradient
+        conic_gradient::conic_gradient_new,
         // solid pattern
         solid_pattern::solid_pattern_from_rgba,
-        solid_pattern::solid_pattern_color,
-        solid_pattern::set_solid_pattern_source,
-        solid_pattern::set_solid_pattern_mask,
+        Yielding::<solid_pattern::solid_pattern_color>,
+        Yielding::<solid_pattern::solid_pattern_set_matrix>,
+        Yielding::<solid_pattern::solid_pattern_matrix>,
+        Yielding::<solid_pattern::solid_pattern_set_extend>,
+        Yielding::<solid_pattern::solid_pattern_extend>,
+        Yielding::<solid_pattern::solid_pattern_set_filter>,
+        Yielding::<solid_pattern::solid_pattern_filter>,
+        // surface pattern
+        Yielding::<surface_pattern::surface_pattern_create>,
+        Yielding::<surface_pattern::surface_pattern_surface>,
+        Yielding::<surface_pattern::surface_pattern_set_matrix>,
+        Yielding::<surface_pattern::surface_pattern_matrix>,
+        Yielding::<surface_pattern::surface_pattern_set_extend>,
+        Yielding::<surface_pattern::surface_pattern_extend>,
+        Yielding::<surface_pattern::surface_pattern_set_filter>,
+        Yielding::<surface_pattern::surface_pattern_filter>,
+        // raster pattern
+        raster_pattern::raster_pattern_new,
+        Yielding::<raster_pattern::raster_pattern_set_matrix>,
+        Yielding::<raster_pattern::raster_pattern_matrix>,
+        Yielding::<raster_pattern::raster_pattern_set_extend>,
+        Yielding::<raster_pattern::raster_pattern_extend>,
+        Yielding::<raster_pattern::raster_pattern_set_filter>,
+        Yielding::<raster_pattern::raster_pattern_filter>,
         // text
-        text::set_font_size,
+        Yielding::<text::set_font_size>,
         text::show_text,
-        text::text_extents,
-        text::set_font_face,
-        text::text_path,
+        Yielding::<text::text_extents>,
+        Yielding::<text::set_font_face>,
+        Yielding::<text::text_path>,
+        text::text_path_along,
+        // font options
+        Yielding::<font_options::set_font_options>,
+        Yielding::<font_options::font_options>,
+        // shaping
+        shaping::font_face_from_file,
+        Yielding::<shaping::set_loaded_font_face>,
+        shaping::shape_text,
+        shaping::show_glyphs,
+        Yielding::<shaping::glyph_path>,
         // matrix
-        matrix::set_font_matrix,
-        matrix::set_matrix,
-        matrix::get_matrix,
-        matrix::user_to_device,
-        matrix::user_to_device_distance,
-        matrix::device_to_user,
-        matrix::device_to_user_distance,
+        Yielding::<matrix::set_font_matrix>,
+        Yielding::<matrix::set_matrix>,
+        Yielding::<matrix::get_matrix>,
+        Yielding::<matrix::user_to_device>,
+        Yielding::<matrix::user_to_device_distance>,
+        Yielding::<matrix::device_to_user>,
+        Yielding::<matrix::device_to_user_distance>,
         matrix::matrix_translate,
         matrix::matrix_scale,
         matrix::matrix_rotate,
@@ -123,27 +225,31 @@ rustler::init!(
         matrix::matrix_transform_point,
         matrix::matrix_transform_distance,
         // transformations
-        transformations::translate,
-        transformations::rotate,
-        transformations::transform,
-        transformations::identity_matrix,
-        transformations::scale,
+        Yielding::<transformations::translate>,
+        Yielding::<transformations::rotate>,
+        Yielding::<transformations::transform>,
+        Yielding::<transformations::identity_matrix>,
+        Yielding::<transformations::scale>,
         // path
-        path::copy_path,
-        path::copy_path_flat,
-        path::append_path,
-        path::get_tolerance,
-        path::set_tolerance
+        Yielding::<path::copy_path>,
+        Yielding::<path::copy_path_flat>,
+        Yielding::<path::path_to_binary>,
+        Yielding::<path::append_path>,
+        Yielding::<path::get_tolerance>,
+        Yielding::<path::set_tolerance>,
     ],
     load = on_load
 );
 
 fn on_load(env: Env, _info: Term) -> bool {
-    rustler::resource!(XairoImage, env);
-    rustler::resource!(XairoPath, env);
-    rustler::resource!(XairoMesh, env);
-    rustler::resource!(XairoLinearGradient, env);
-    rustler::resource!(XairoRadialGradient, env);
-    rustler::resource!(XairoSolidPattern, env);
+    rustler::resource!(Guarded<XairoImage>, env);
+    rustler::resource!(Guarded<XairoPath>, env);
+    rustler::resource!(Guarded<XairoMesh>, env);
+    rustler::resource!(Guarded<XairoLinearGradient>, env);
+    rustler::resource!(Guarded<XairoRadialGradient>, env);
+    rustler::resource!(Guarded<XairoSolidPattern>, env);
+    rustler::resource!(Guarded<XairoSurfacePattern>, env);
+    rustler::resource!(Guarded<XairoRasterPattern>, env);
+    rustler::resource!(Guarded<XairoFontFace>, env);
     true
 }
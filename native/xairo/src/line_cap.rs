@@ -1,4 +1,4 @@
-use crate::xairo_image::ImageArc;
+use crate::xairo_image::{ImageArc, ImageResult};
 
 #[derive(Copy,Clone,Debug,NifUnitEnum)]
 enum LineCap {
@@ -9,10 +9,10 @@ enum LineCap {
 }
 
 #[rustler::nif]
-fn set_line_cap(image: ImageArc, line_cap: LineCap) -> ImageArc {
+fn set_line_cap(image: ImageArc, line_cap: LineCap) -> ImageResult {
     let line_cap = match_line_cap(line_cap);
-    image.context.set_line_cap(line_cap);
-    image
+    image.lock()?.context.set_line_cap(line_cap);
+    Ok(image)
 }
 
 fn match_line_cap(line_cap: LineCap) -> cairo::LineCap {
@@ -1,4 +1,4 @@
-use crate::xairo_image::ImageArc;
+use crate::xairo_image::{ImageArc, ImageResult};
 
 #[derive(Copy,Clone,Debug,NifUnitEnum)]
 enum LineJoin {
@@ -9,10 +9,10 @@ enum LineJoin {
 }
 
 #[rustler::nif]
-fn set_line_join(image: ImageArc, line_join: LineJoin) -> ImageArc {
+fn set_line_join(image: ImageArc, line_join: LineJoin) -> ImageResult {
     let line_join = match_line_join(line_join);
-    image.context.set_line_join(line_join);
-    image
+    image.lock()?.context.set_line_join(line_join);
+    Ok(image)
 }
 
 fn match_line_join(line_join: LineJoin) -> cairo::LineJoin {
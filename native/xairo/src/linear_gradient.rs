@@ -1,28 +1,32 @@
 use crate::color::Rgba;
 use crate::error::Error;
+use crate::gradient::{self, ColorStop};
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
+use crate::resource::{Guardable, Guarded};
 use crate::shapes::Point;
-use crate::xairo_image::{ImageArc, ImageResult};
 use rustler::ResourceArc;
 
 pub struct XairoLinearGradient {
     pub gradient: cairo::LinearGradient,
 }
 
-unsafe impl Send for XairoLinearGradient {}
-unsafe impl Sync for XairoLinearGradient {}
+// SAFETY: the pattern is only shared with the contexts it is set on as a
+// source, and `set_source` joins each of those images into its lock group.
+unsafe impl Guardable for XairoLinearGradient {}
 
-pub type LinearGradientArc = ResourceArc<XairoLinearGradient>;
+pub type LinearGradientArc = ResourceArc<Guarded<XairoLinearGradient>>;
 
 #[rustler::nif]
-fn linear_gradient_new(start_point: Point, stop_point: Point) -> LinearGradientArc {
-    ResourceArc::new(XairoLinearGradient {
+fn linear_gradient_new(start_point: Point, stop_point: Point) -> Result<LinearGradientArc, Error> {
+    Ok(ResourceArc::new(Guarded::new(XairoLinearGradient {
         gradient: cairo::LinearGradient::new(
             start_point.x,
             start_point.y,
             stop_point.x,
             stop_point.y,
         ),
-    })
+    })))
 }
 
 #[rustler::nif]
@@ -30,53 +34,99 @@ fn linear_gradient_add_color_stop(
     gradient: LinearGradientArc,
     offset: f64,
     color: Rgba,
-) -> LinearGradientArc {
+) -> Result<LinearGradientArc, Error> {
     let (r, g, b, a) = color.to_tuple();
-    gradient.gradient.add_color_stop_rgba(offset, r, g, b, a);
     gradient
+        .lock()?
+        .gradient
+        .add_color_stop_rgba(offset, r, g, b, a);
+    Ok(gradient)
 }
 
 #[rustler::nif]
 fn linear_gradient_color_stop_count(gradient: LinearGradientArc) -> Result<isize, Error> {
-    match gradient.gradient.color_stop_count() {
+    match gradient.lock()?.gradient.color_stop_count() {
         Ok(count) => Ok(count),
         Err(_) => Err(Error::ColorStopCount),
     }
 }
 
 #[rustler::nif]
-fn linear_gradient_color_stop(gradient: LinearGradientArc, index: isize) -> Result<Rgba, Error> {
-    match gradient.gradient.color_stop_rgba(index) {
-        Ok((_, red, green, blue, alpha)) => Ok(Rgba {
-            red,
-            green,
-            blue,
-            alpha,
-        }),
-        Err(_) => Err(Error::ColorStop(index)),
-    }
+fn linear_gradient_color_stop(
+    gradient: LinearGradientArc,
+    index: isize,
+) -> Result<ColorStop, Error> {
+    gradient::color_stop(&gradient.lock()?.gradient, index)
+}
+
+#[rustler::nif]
+fn linear_gradient_color_stops(gradient: LinearGradientArc) -> Result<Vec<ColorStop>, Error> {
+    gradient::color_stops(&gradient.lock()?.gradient)
+}
+
+#[rustler::nif]
+fn linear_gradient_set_color_stops(
+    gradient: LinearGradientArc,
+    stops: Vec<ColorStop>,
+) -> Result<LinearGradientArc, Error> {
+    let guard = gradient.lock()?;
+    let rebuilt = match guard.gradient.linear_points() {
+        Ok((x1, y1, x2, y2)) => cairo::LinearGradient::new(x1, y1, x2, y2),
+        Err(_) => return Err(Error::LinearPoints),
+    };
+    gradient::copy_with_color_stops(&guard.gradient, &rebuilt, &stops);
+    Ok(ResourceArc::new(Guarded::new(XairoLinearGradient {
+        gradient: rebuilt,
+    })))
 }
 
 #[rustler::nif]
 fn linear_gradient_linear_points(gradient: LinearGradientArc) -> Result<(Point, Point), Error> {
-    match gradient.gradient.linear_points() {
+    match gradient.lock()?.gradient.linear_points() {
         Ok((x1, y1, x2, y2)) => Ok((Point { x: x1, y: y1 }, Point { x: x2, y: y2 })),
         Err(_) => Err(Error::LinearPoints),
     }
 }
 
 #[rustler::nif]
-fn set_linear_gradient_source(image: ImageArc, gradient: LinearGradientArc) -> ImageResult {
-    match image.context.set_source(&gradient.gradient) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::SetSource("linear gradient")),
-    }
+fn linear_gradient_set_matrix(
+    gradient: LinearGradientArc,
+    matrix: Matrix,
+) -> Result<LinearGradientArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    gradient.lock()?.gradient.set_matrix(matrix);
+    Ok(gradient)
 }
 
 #[rustler::nif]
-fn set_linear_gradient_mask(image: ImageArc, gradient: LinearGradientArc) -> ImageResult {
-    match image.context.mask(&gradient.gradient) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::MaskError),
-    }
+fn linear_gradient_matrix(gradient: LinearGradientArc) -> Result<Matrix, Error> {
+    Ok(gradient.lock()?.gradient.matrix().into())
+}
+
+#[rustler::nif]
+fn linear_gradient_set_extend(
+    gradient: LinearGradientArc,
+    extend: Extend,
+) -> Result<LinearGradientArc, Error> {
+    gradient.lock()?.gradient.set_extend(extend.into());
+    Ok(gradient)
+}
+
+#[rustler::nif]
+fn linear_gradient_extend(gradient: LinearGradientArc) -> Result<Extend, Error> {
+    Ok(gradient.lock()?.gradient.extend().into())
+}
+
+#[rustler::nif]
+fn linear_gradient_set_filter(
+    gradient: LinearGradientArc,
+    filter: Filter,
+) -> Result<LinearGradientArc, Error> {
+    gradient.lock()?.gradient.set_filter(filter.into());
+    Ok(gradient)
+}
+
+#[rustler::nif]
+fn linear_gradient_filter(gradient: LinearGradientArc) -> Result<Filter, Error> {
+    Ok(gradient.lock()?.gradient.filter().into())
 }
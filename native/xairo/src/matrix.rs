@@ -1,5 +1,6 @@
-use crate::xairo_image::ImageArc;
+use crate::xairo_image::{ImageArc, ImageResult};
 use crate::shapes::{Point, Vector};
+use crate::status;
 use crate::error::Error;
 
 #[derive(Copy,Clone,Debug,NifStruct)]
@@ -39,35 +40,54 @@ impl From<cairo::Matrix> for Matrix {
     }
 }
 
+/// Converts the matrix for use as a pattern matrix, which cairo requires to
+/// be invertible.
+pub fn pattern_matrix(matrix: Matrix) -> Result<cairo::Matrix, Error> {
+    let matrix = cairo::Matrix::from(matrix);
+    match matrix.try_invert() {
+        Ok(_) => Ok(matrix),
+        Err(_) => Err(Error::UninvertibleMatrix),
+    }
+}
+
 #[rustler::nif]
-fn set_font_matrix(image: ImageArc, matrix: Matrix) -> ImageArc {
+fn set_font_matrix(image: ImageArc, matrix: Matrix) -> ImageResult {
     let matrix = cairo::Matrix::from(matrix);
-    image.context.set_font_matrix(matrix);
-    image
+    {
+        let guard = image.lock()?;
+        guard.context.set_font_matrix(matrix);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn set_matrix(image: ImageArc, matrix: Matrix) -> ImageArc {
+fn set_matrix(image: ImageArc, matrix: Matrix) -> ImageResult {
     let matrix = cairo::Matrix::from(matrix);
-    image.context.set_matrix(matrix);
-    image
+    {
+        let guard = image.lock()?;
+        guard.context.set_matrix(matrix);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn get_matrix(image: ImageArc) -> Matrix {
-    let matrix: cairo::Matrix = image.context.matrix();
-    matrix.into()
+fn get_matrix(image: ImageArc) -> Result<Matrix, Error> {
+    let matrix: cairo::Matrix = image.lock()?.context.matrix();
+    Ok(matrix.into())
 }
 
 #[rustler::nif]
-fn user_to_device(image: ImageArc, point: Point) -> Point {
-    let (x, y) = image.context.user_to_device(point.x, point.y);
-    Point { x, y }
+fn user_to_device(image: ImageArc, point: Point) -> Result<Point, Error> {
+    let (x, y) = image.lock()?.context.user_to_device(point.x, point.y);
+    Ok(Point { x, y })
 }
 
 #[rustler::nif]
 fn user_to_device_distance(image: ImageArc, vector: Vector) -> Result<Vector, Error> {
-    match image.context.user_to_device_distance(vector.x, vector.y) {
+    let guard = image.lock()?;
+    match guard.context.user_to_device_distance(vector.x, vector.y) {
         Ok((x, y)) => Ok(Vector{ x, y }),
         Err(_) => Err(Error::TranslatedVector)
     }
@@ -75,7 +95,7 @@ fn user_to_device_distance(image: ImageArc, vector: Vector) -> Result<Vector, Er
 
 #[rustler::nif]
 fn device_to_user(image: ImageArc, point: Point) -> Result<Point, Error> {
-    match image.context.device_to_user(point.x, point.y) {
+    match image.lock()?.context.device_to_user(point.x, point.y) {
         Ok((x, y)) => Ok(Point{ x, y }),
         Err(_) => Err(Error::TranslatedPoint)
     }
@@ -83,31 +103,32 @@ fn device_to_user(image: ImageArc, point: Point) -> Result<Point, Error> {
 
 #[rustler::nif]
 fn device_to_user_distance(image: ImageArc, vector: Vector) -> Result<Vector, Error> {
-    match image.context.device_to_user_distance(vector.x, vector.y) {
+    let guard = image.lock()?;
+    match guard.context.device_to_user_distance(vector.x, vector.y) {
         Ok((x, y)) => Ok(Vector{ x, y }),
         Err(_) => Err(Error::TranslatedVector)
     }
 }
 
 #[rustler::nif]
-fn matrix_translate(matrix: Matrix, xt: f64, yt: f64) -> Matrix {
+fn matrix_translate(matrix: Matrix, xt: f64, yt: f64) -> Result<Matrix, Error> {
     let mut matrix = cairo::Matrix::from(matrix);
     matrix.translate(xt, yt);
-    matrix.into()
+    Ok(matrix.into())
 }
 
 #[rustler::nif]
-fn matrix_scale(matrix: Matrix, xx: f64, yy: f64) -> Matrix {
+fn matrix_scale(matrix: Matrix, xx: f64, yy: f64) -> Result<Matrix, Error> {
     let mut matrix = cairo::Matrix::from(matrix);
     matrix.scale(xx, yy);
-    matrix.into()
+    Ok(matrix.into())
 }
 
 #[rustler::nif]
-fn matrix_rotate(matrix: Matrix, radians: f64) -> Matrix {
+fn matrix_rotate(matrix: Matrix, radians: f64) -> Result<Matrix, Error> {
     let mut matrix = cairo::Matrix::from(matrix);
     matrix.rotate(radians);
-    matrix.into()
+    Ok(matrix.into())
 }
 
 #[rustler::nif]
@@ -120,23 +141,23 @@ fn matrix_invert(matrix: Matrix) -> Result<Matrix, Error> {
 }
 
 #[rustler::nif]
-fn matrix_multiply(matrix1: Matrix, matrix2: Matrix) -> Matrix {
+fn matrix_multiply(matrix1: Matrix, matrix2: Matrix) -> Result<Matrix, Error> {
     let matrix1 = cairo::Matrix::from(matrix1);
     let matrix2 = cairo::Matrix::from(matrix2);
     let matrix3 = cairo::Matrix::multiply(&matrix1, &matrix2);
-    matrix3.into()
+    Ok(matrix3.into())
 }
 
 #[rustler::nif]
-fn matrix_transform_point(matrix: Matrix, point: Point) -> Point {
+fn matrix_transform_point(matrix: Matrix, point: Point) -> Result<Point, Error> {
     let matrix = cairo::Matrix::from(matrix);
     let (x, y) = matrix.transform_point(point.x, point.y);
-    Point { x, y }
+    Ok(Point { x, y })
 }
 
 #[rustler::nif]
-fn matrix_transform_distance(matrix: Matrix, vector: Vector) -> Vector {
+fn matrix_transform_distance(matrix: Matrix, vector: Vector) -> Result<Vector, Error> {
     let matrix = cairo::Matrix::from(matrix);
     let (x, y) = matrix.transform_distance(vector.x, vector.y);
-    Vector { x, y }
+    Ok(Vector { x, y })
 }
@@ -1,70 +1,153 @@
 use crate::color::Rgba;
 use crate::error::Error;
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
 use crate::path::{PathArc, XairoPath};
+use crate::resource::{Guardable, Guarded};
 use crate::shapes::Point;
-use crate::xairo_image::{ImageArc, ImageResult};
+use crate::status;
 use cairo::MeshCorner;
 use rustler::ResourceArc;
 
 pub struct XairoMesh {
     pub mesh: cairo::Mesh,
+    /// The patch currently being built, if any.
+    patch: Option<Patch>,
 }
 
-unsafe impl Send for XairoMesh {}
-unsafe impl Sync for XairoMesh {}
+// SAFETY: the pattern is only shared with the contexts it is set on as a
+// source, and `set_source` joins each of those images into its lock group.
+unsafe impl Guardable for XairoMesh {}
 
-pub type MeshArc = ResourceArc<XairoMesh>;
+/// Tracks how far the current patch has been built, so that calls made out
+/// of order are reported as errors instead of putting the mesh into an error
+/// state that only surfaces once it is drawn.
+#[derive(Default)]
+struct Patch {
+    started: bool,
+    sides: usize,
+}
+
+impl XairoMesh {
+    pub fn new(mesh: cairo::Mesh) -> Self {
+        Self { mesh, patch: None }
+    }
+
+    fn patch(&mut self) -> Result<&mut Patch, Error> {
+        self.patch
+            .as_mut()
+            .ok_or(Error::MeshConstruction("no patch in progress"))
+    }
+
+    /// Records a new side of the current patch. As in cairo, a side added
+    /// before the patch has a starting point only sets that point.
+    fn add_side(&mut self) -> Result<(), Error> {
+        let patch = self.patch()?;
+        if !patch.started {
+            patch.started = true;
+        } else if patch.sides == 4 {
+            return Err(Error::MeshConstruction("patch already has 4 sides"));
+        } else {
+            patch.sides += 1;
+        }
+        Ok(())
+    }
+}
+
+pub type MeshArc = ResourceArc<Guarded<XairoMesh>>;
 
 #[rustler::nif]
-fn mesh_new() -> MeshArc {
-    ResourceArc::new(XairoMesh {
-        mesh: cairo::Mesh::new(),
-    })
+fn mesh_new() -> Result<MeshArc, Error> {
+    Ok(ResourceArc::new(Guarded::new(XairoMesh::new(
+        cairo::Mesh::new(),
+    ))))
 }
 
 #[rustler::nif]
-fn mesh_begin_patch(mesh: MeshArc) -> MeshArc {
-    mesh.mesh.begin_patch();
-    mesh
+fn mesh_begin_patch(mesh: MeshArc) -> Result<MeshArc, Error> {
+    {
+        let mut guard = mesh.lock()?;
+        if guard.patch.is_some() {
+            return Err(Error::MeshConstruction("patch already in progress"));
+        }
+        guard.mesh.begin_patch();
+        guard.patch = Some(Patch::default());
+    }
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn mesh_end_patch(mesh: MeshArc) -> MeshArc {
-    mesh.mesh.end_patch();
-    mesh
+fn mesh_end_patch(mesh: MeshArc) -> Result<MeshArc, Error> {
+    {
+        let mut guard = mesh.lock()?;
+        if !guard.patch()?.started {
+            return Err(Error::MeshConstruction("patch has no points"));
+        }
+        guard.patch = None;
+        guard.mesh.end_patch();
+        status::check_pattern(&guard.mesh)?;
+    }
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn mesh_move_to(mesh: MeshArc, point: Point) -> MeshArc {
-    mesh.mesh.move_to(point.x, point.y);
-    mesh
+fn mesh_move_to(mesh: MeshArc, point: Point) -> Result<MeshArc, Error> {
+    {
+        let mut guard = mesh.lock()?;
+        let patch = guard.patch()?;
+        if patch.sides > 0 {
+            return Err(Error::MeshConstruction(
+                "move_to after the first side of a patch",
+            ));
+        }
+        patch.started = true;
+        guard.mesh.move_to(point.x, point.y);
+    }
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn mesh_line_to(mesh: MeshArc, point: Point) -> MeshArc {
-    mesh.mesh.line_to(point.x, point.y);
-    mesh
+fn mesh_line_to(mesh: MeshArc, point: Point) -> Result<MeshArc, Error> {
+    {
+        let mut guard = mesh.lock()?;
+        guard.add_side()?;
+        guard.mesh.line_to(point.x, point.y);
+    }
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn mesh_curve_to(mesh: MeshArc, point1: Point, point2: Point, point3: Point) -> MeshArc {
-    mesh.mesh
-        .curve_to(point1.x, point1.y, point2.x, point2.y, point3.x, point3.y);
-    mesh
+fn mesh_curve_to(
+    mesh: MeshArc,
+    point1: Point,
+    point2: Point,
+    point3: Point,
+) -> Result<MeshArc, Error> {
+    {
+        let mut guard = mesh.lock()?;
+        guard.add_side()?;
+        guard
+            .mesh
+            .curve_to(point1.x, point1.y, point2.x, point2.y, point3.x, point3.y);
+    }
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn mesh_set_control_point(mesh: MeshArc, index: usize, point: Point) -> MeshArc {
-    if let Some(corner) = mesh_corner(index) {
-        mesh.mesh.set_control_point(corner, point.x, point.y);
+fn mesh_set_control_point(mesh: MeshArc, index: usize, point: Point) -> Result<MeshArc, Error> {
+    let corner = mesh_corner(index).ok_or(Error::MeshCorner(index))?;
+    {
+        let mut guard = mesh.lock()?;
+        guard.patch()?;
+        guard.mesh.set_control_point(corner, point.x, point.y);
     }
-    mesh
+    Ok(mesh)
 }
 
 #[rustler::nif]
 fn mesh_control_point(mesh: MeshArc, patch_num: usize, corner: usize) -> Result<Point, Error> {
     if let Some(mesh_corner) = mesh_corner(corner) {
-        match mesh.mesh.control_point(patch_num, mesh_corner) {
+        match mesh.lock()?.mesh.control_point(patch_num, mesh_corner) {
             Ok((x, y)) => Ok(Point { x, y }),
             Err(_) => Err(Error::ControlPointError(corner, patch_num)),
         }
@@ -74,18 +157,21 @@ fn mesh_control_point(mesh: MeshArc, patch_num: usize, corner: usize) -> Result<
 }
 
 #[rustler::nif]
-fn mesh_set_corner_color(mesh: MeshArc, index: usize, rgba: Rgba) -> MeshArc {
-    if let Some(corner) = mesh_corner(index) {
+fn mesh_set_corner_color(mesh: MeshArc, index: usize, rgba: Rgba) -> Result<MeshArc, Error> {
+    let corner = mesh_corner(index).ok_or(Error::MeshCorner(index))?;
+    {
+        let mut guard = mesh.lock()?;
+        guard.patch()?;
         let (r, g, b, a) = rgba.to_tuple();
-        mesh.mesh.set_corner_color_rgba(corner, r, g, b, a);
+        guard.mesh.set_corner_color_rgba(corner, r, g, b, a);
     }
-    mesh
+    Ok(mesh)
 }
 
 #[rustler::nif]
 fn mesh_corner_color(mesh: MeshArc, patch_num: usize, corner: usize) -> Result<Rgba, Error> {
     if let Some(mesh_corner) = mesh_corner(corner) {
-        match mesh.mesh.corner_color_rgba(patch_num, mesh_corner) {
+        match mesh.lock()?.mesh.corner_color_rgba(patch_num, mesh_corner) {
             Ok((red, green, blue, alpha)) => Ok(Rgba {
                 red,
                 green,
@@ -101,7 +187,7 @@ fn mesh_corner_color(mesh: MeshArc, patch_num: usize, corner: usize) -> Result<R
 
 #[rustler::nif]
 fn mesh_patch_count(mesh: MeshArc) -> Result<usize, Error> {
-    match mesh.mesh.patch_count() {
+    match mesh.lock()?.mesh.patch_count() {
         Ok(count) => Ok(count),
         Err(_) => Err(Error::PatchCount)
     }
@@ -109,35 +195,55 @@ fn mesh_patch_count(mesh: MeshArc) -> Result<usize, Error> {
 
 #[rustler::nif]
 fn mesh_path(mesh: MeshArc, patch_num: usize) -> Result<PathArc, Error> {
-    match mesh.mesh.path(patch_num) {
-        Ok(path) => Ok(ResourceArc::new(XairoPath { path })),
+    match mesh.lock()?.mesh.path(patch_num) {
+        Ok(path) => Ok(ResourceArc::new(Guarded::new(XairoPath { path }))),
         Err(_) => Err(Error::MeshPath(patch_num))
     }
 
 }
 
 #[rustler::nif]
-fn set_mesh_source(image: ImageArc, mesh: MeshArc) -> ImageResult {
-    match image.context.set_source(&mesh.mesh) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::SetSource("mesh")),
-    }
+fn mesh_set_matrix(mesh: MeshArc, matrix: Matrix) -> Result<MeshArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    mesh.lock()?.mesh.set_matrix(matrix);
+    Ok(mesh)
 }
 
 #[rustler::nif]
-fn set_mesh_mask(image: ImageArc, mesh: MeshArc) -> ImageResult {
-    match image.context.mask(&mesh.mesh) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::SetSource("mesh")),
-    }
+fn mesh_matrix(mesh: MeshArc) -> Result<Matrix, Error> {
+    Ok(mesh.lock()?.mesh.matrix().into())
+}
+
+#[rustler::nif]
+fn mesh_set_extend(mesh: MeshArc, extend: Extend) -> Result<MeshArc, Error> {
+    mesh.lock()?.mesh.set_extend(extend.into());
+    Ok(mesh)
+}
+
+#[rustler::nif]
+fn mesh_extend(mesh: MeshArc) -> Result<Extend, Error> {
+    Ok(mesh.lock()?.mesh.extend().into())
 }
 
+#[rustler::nif]
+fn mesh_set_filter(mesh: MeshArc, filter: Filter) -> Result<MeshArc, Error> {
+    mesh.lock()?.mesh.set_filter(filter.into());
+    Ok(mesh)
+}
+
+#[rustler::nif]
+fn mesh_filter(mesh: MeshArc) -> Result<Filter, Error> {
+    Ok(mesh.lock()?.mesh.filter().into())
+}
+
+/// The corners of a mesh patch, in the order cairo numbers them.
+pub const CORNERS: [MeshCorner; 4] = [
+    MeshCorner::MeshCorner0,
+    MeshCorner::MeshCorner1,
+    MeshCorner::MeshCorner2,
+    MeshCorner::MeshCorner3,
+];
+
 fn mesh_corner(index: usize) -> Option<MeshCorner> {
-    match index {
-        0 => Some(MeshCorner::MeshCorner0),
-        1 => Some(MeshCorner::MeshCorner1),
-        2 => Some(MeshCorner::MeshCorner2),
-        3 => Some(MeshCorner::MeshCorner3),
-        _ => None,
-    }
+    CORNERS.get(index).copied()
 }
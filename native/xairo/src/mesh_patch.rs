@@ -0,0 +1,135 @@
+use crate::color::Rgba;
+use crate::error::Error;
+use crate::mesh::{MeshArc, XairoMesh, CORNERS};
+use crate::resource::Guarded;
+use crate::shapes::{Curve, CurveControlPoint, Point};
+use crate::status;
+use cairo::PathSegment;
+use rustler::ResourceArc;
+
+/// A side of a mesh patch, drawn from the end of the previous side (or the
+/// start of the patch) as a straight line or a Bézier curve.
+#[derive(Copy, Clone, Debug, NifUntaggedEnum)]
+pub enum Side {
+    Line(Point),
+    Curve(Curve),
+}
+
+/// A complete description of a single mesh patch.
+///
+/// `control_points` and `corner_colors` are indexed by corner, and a `nil`
+/// control point keeps the default cairo calculates from the sides.
+#[derive(Debug, NifStruct)]
+#[module = "Xairo.Pattern.Mesh.Patch"]
+pub struct MeshPatch {
+    pub start: Point,
+    pub sides: Vec<Side>,
+    pub control_points: Vec<Option<Point>>,
+    pub corner_colors: Vec<Rgba>,
+}
+
+fn point((x, y): (f64, f64)) -> Point {
+    Point { x, y }
+}
+
+fn add_patch(mesh: &cairo::Mesh, patch: &MeshPatch) -> Result<(), Error> {
+    if patch.sides.len() > 4 {
+        return Err(Error::MeshConstruction("patch has more than 4 sides"));
+    }
+    if patch.control_points.len() > 4 {
+        return Err(Error::MeshConstruction(
+            "patch has more than 4 control points",
+        ));
+    }
+    if patch.corner_colors.len() != 4 {
+        return Err(Error::MeshConstruction("patch needs 4 corner colors"));
+    }
+
+    mesh.begin_patch();
+    mesh.move_to(patch.start.x, patch.start.y);
+    for side in &patch.sides {
+        match *side {
+            Side::Line(point) => mesh.line_to(point.x, point.y),
+            Side::Curve(curve) => {
+                let ((x1, y1), (x2, y2), (x3, y3)) = curve.to_tuple();
+                mesh.curve_to(x1, y1, x2, y2, x3, y3);
+            }
+        }
+    }
+    for (&corner, point) in CORNERS.iter().zip(&patch.control_points) {
+        if let Some(point) = point {
+            mesh.set_control_point(corner, point.x, point.y);
+        }
+    }
+    for (&corner, color) in CORNERS.iter().zip(&patch.corner_colors) {
+        let (r, g, b, a) = color.to_tuple();
+        mesh.set_corner_color_rgba(corner, r, g, b, a);
+    }
+    mesh.end_patch();
+    status::check_pattern(mesh)
+}
+
+/// Reads back the patch at `index`. cairo stores every side as a curve, so
+/// sides added with `line_to` are returned as curves along the same line.
+fn patch(mesh: &cairo::Mesh, index: usize) -> Result<MeshPatch, Error> {
+    let path = mesh.path(index).map_err(|_| Error::MeshPath(index))?;
+
+    let mut start = None;
+    let mut sides = Vec::new();
+    for segment in path.iter() {
+        match segment {
+            PathSegment::MoveTo(to) => start = Some(point(to)),
+            PathSegment::LineTo(to) => sides.push(Side::Line(point(to))),
+            PathSegment::CurveTo(p1, p2, p3) => sides.push(Side::Curve(Curve {
+                first_control_point: CurveControlPoint::Point(point(p1)),
+                second_control_point: CurveControlPoint::Point(point(p2)),
+                curve_end: CurveControlPoint::Point(point(p3)),
+            })),
+            PathSegment::ClosePath => {}
+        }
+    }
+    let start = start.ok_or(Error::MeshPath(index))?;
+
+    let mut control_points = Vec::with_capacity(4);
+    let mut corner_colors = Vec::with_capacity(4);
+    for (corner_index, &corner) in CORNERS.iter().enumerate() {
+        match mesh.control_point(index, corner) {
+            Ok(control_point) => control_points.push(Some(point(control_point))),
+            Err(_) => return Err(Error::ControlPointError(corner_index, index)),
+        }
+        match mesh.corner_color_rgba(index, corner) {
+            Ok((red, green, blue, alpha)) => corner_colors.push(Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            }),
+            Err(_) => return Err(Error::CornerColorError(corner_index, index)),
+        }
+    }
+
+    Ok(MeshPatch {
+        start,
+        sides,
+        control_points,
+        corner_colors,
+    })
+}
+
+/// Builds a mesh from a list of complete patch descriptions in one call.
+#[rustler::nif]
+fn mesh_from_patches(patches: Vec<MeshPatch>) -> Result<MeshArc, Error> {
+    let mesh = cairo::Mesh::new();
+    for (index, patch) in patches.iter().enumerate() {
+        add_patch(&mesh, patch).map_err(|error| Error::MeshPatch(index, Box::new(error)))?;
+    }
+    Ok(ResourceArc::new(Guarded::new(XairoMesh::new(mesh))))
+}
+
+/// Returns every patch of the mesh, in the form accepted by `mesh_from_patches`.
+#[rustler::nif]
+fn mesh_patches(mesh: MeshArc) -> Result<Vec<MeshPatch>, Error> {
+    let guard = mesh.lock()?;
+    let count = guard.mesh.patch_count().map_err(|_| Error::PatchCount)?;
+    (0..count).map(|index| patch(&guard.mesh, index)).collect()
+}
@@ -0,0 +1,45 @@
+use crate::error::Error;
+use rustler::{Binary, Env, OwnedBinary};
+
+/// Number of bytes used to store a single x/y pair: two little-endian f64s.
+const POINT_SIZE: usize = 16;
+
+/// Decodes a binary of packed little-endian f64 x/y pairs into a list of
+/// coordinates.
+pub fn decode_points(data: &[u8]) -> Result<Vec<(f64, f64)>, Error> {
+    let chunks = data.chunks_exact(POINT_SIZE);
+    if !chunks.remainder().is_empty() {
+        return Err(Error::PointData(data.len()));
+    }
+
+    Ok(chunks
+        .map(|chunk| {
+            let mut x = [0; 8];
+            let mut y = [0; 8];
+            x.copy_from_slice(&chunk[..8]);
+            y.copy_from_slice(&chunk[8..]);
+            (f64::from_le_bytes(x), f64::from_le_bytes(y))
+        })
+        .collect())
+}
+
+/// Encodes a list of coordinates as packed little-endian f64 x/y pairs.
+pub fn encode_points(points: &[(f64, f64)]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(points.len() * POINT_SIZE);
+    for (x, y) in points {
+        data.extend_from_slice(&x.to_le_bytes());
+        data.extend_from_slice(&y.to_le_bytes());
+    }
+    data
+}
+
+/// Copies `data` into a new binary owned by `env`.
+pub fn to_binary<'a>(env: Env<'a>, data: &[u8]) -> Result<Binary<'a>, Error> {
+    match OwnedBinary::new(data.len()) {
+        Some(mut binary) => {
+            binary.as_mut_slice().copy_from_slice(data);
+            Ok(binary.release(env))
+        }
+        None => Err(Error::BinaryAlloc),
+    }
+}
@@ -1,49 +1,176 @@
-use cairo::Path;
-use crate::xairo_image::ImageArc;
-use rustler::ResourceArc;
+use cairo::{Context, Format, ImageSurface, Path, PathSegment};
+use crate::packed;
+use crate::xairo_image::{ImageArc, ImageResult};
+use rustler::{Binary, Env, ResourceArc};
 use crate::error::Error;
+use crate::resource::{self, Guardable, Guarded};
 
 #[derive(Debug)]
 pub struct XairoPath {
     pub path: Path
 }
 
-unsafe impl Send for XairoPath { }
-unsafe impl Sync for XairoPath { }
+// SAFETY: a cairo path is a plain copy of path data, which cairo copies again
+// when it is appended to a context, so nothing else ever reaches it.
+unsafe impl Guardable for XairoPath {}
 
-pub type PathArc = ResourceArc<XairoPath>;
+pub type PathArc = ResourceArc<Guarded<XairoPath>>;
 pub type PathResult = Result<PathArc, Error>;
 
+pub type Segment = ((f64, f64), (f64, f64));
+
+impl XairoPath {
+    /// Returns a copy of the path with its curves replaced by straight lines,
+    /// as flattened by cairo at its default tolerance.
+    fn flattened(&self) -> Result<Path, Error> {
+        let surface = ImageSurface::create(Format::ARgb32, 0, 0).map_err(|_| Error::CopyPath)?;
+        let context = Context::new(&surface).map_err(|_| Error::CopyPath)?;
+        context.append_path(&self.path);
+        context.copy_path_flat().map_err(|_| Error::CopyPath)
+    }
+
+    /// Returns the path as a list of straight line segments, approximating
+    /// curves and skipping the gaps between subpaths.
+    pub fn segments(&self) -> Result<Vec<Segment>, Error> {
+        let mut segments = Vec::new();
+        let mut start = (0.0, 0.0);
+        let mut current = (0.0, 0.0);
+
+        for segment in self.flattened()?.iter() {
+            match segment {
+                PathSegment::MoveTo(point) => {
+                    start = point;
+                    current = point;
+                }
+                PathSegment::LineTo(point) => {
+                    segments.push((current, point));
+                    current = point;
+                }
+                PathSegment::CurveTo(_, _, point) => {
+                    segments.push((current, point));
+                    current = point;
+                }
+                PathSegment::ClosePath => {
+                    segments.push((current, start));
+                    current = start;
+                }
+            }
+        }
+
+        Ok(segments)
+    }
+
+    /// Returns the points of each subpath in order, approximating curves with
+    /// straight lines. A closed subpath ends with its starting point again,
+    /// unless its last point already is the starting point.
+    pub fn subpaths(&self) -> Result<Vec<Vec<(f64, f64)>>, Error> {
+        let mut subpaths: Vec<Vec<(f64, f64)>> = Vec::new();
+        let mut start = (0.0, 0.0);
+
+        for segment in self.flattened()?.iter() {
+            match segment {
+                PathSegment::MoveTo(point) => {
+                    // cairo follows every `ClosePath` with a `MoveTo` to the
+                    // start of the closed subpath, and a `MoveTo` that isn't
+                    // followed by a line doesn't add anything to the path.
+                    match subpaths.last_mut() {
+                        Some(points) if points.len() == 1 => points[0] = point,
+                        _ => subpaths.push(vec![point]),
+                    }
+                    start = point;
+                }
+                PathSegment::LineTo(point) | PathSegment::CurveTo(_, _, point) => {
+                    match subpaths.last_mut() {
+                        Some(points) => points.push(point),
+                        None => subpaths.push(vec![point]),
+                    }
+                }
+                PathSegment::ClosePath => {
+                    if let Some(points) = subpaths.last_mut() {
+                        if points.len() > 1 && points.last() != Some(&start) {
+                            points.push(start);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(points) = subpaths.last() {
+            if points.len() == 1 {
+                subpaths.pop();
+            }
+        }
+
+        Ok(subpaths)
+    }
+}
+
+pub fn segment_length(((x1, y1), (x2, y2)): &Segment) -> f64 {
+    (x2 - x1).hypot(y2 - y1)
+}
+
+/// Returns the point at `distance` along the segments, and the angle of the
+/// path's tangent at that point, or `None` if the distance falls outside the path.
+pub fn point_at_length(segments: &[Segment], distance: f64) -> Option<((f64, f64), f64)> {
+    if distance < 0.0 {
+        return None;
+    }
+
+    let mut remaining = distance;
+    for segment in segments {
+        let length = segment_length(segment);
+        if remaining <= length && length > 0.0 {
+            let ((x1, y1), (x2, y2)) = *segment;
+            let t = remaining / length;
+            let point = (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t);
+            return Some((point, (y2 - y1).atan2(x2 - x1)));
+        }
+        remaining -= length;
+    }
+    None
+}
 
 #[rustler::nif]
 fn copy_path(image: ImageArc) -> PathResult {
-    match image.context.copy_path() {
-        Ok(path) => Ok(ResourceArc::new(XairoPath { path })),
+    match image.lock()?.context.copy_path() {
+        Ok(path) => Ok(ResourceArc::new(Guarded::new(XairoPath { path }))),
         Err(_) => Err(Error::CopyPath)
     }
 }
 
 #[rustler::nif]
 fn copy_path_flat(image: ImageArc) -> PathResult {
-    match image.context.copy_path_flat() {
-        Ok(path) => Ok(ResourceArc::new(XairoPath { path })),
+    match image.lock()?.context.copy_path_flat() {
+        Ok(path) => Ok(ResourceArc::new(Guarded::new(XairoPath { path }))),
         Err(_) => Err(Error::CopyPath)
     }
 }
 
 #[rustler::nif]
-fn append_path(image: ImageArc, path: PathArc) -> ImageArc {
-    image.context.append_path(&path.path);
-    image
+fn path_to_binary<'a>(env: Env<'a>, path: PathArc) -> Result<Vec<Binary<'a>>, Error> {
+    path.lock()?
+        .subpaths()?
+        .iter()
+        .map(|points| packed::to_binary(env, &packed::encode_points(points)))
+        .collect()
+}
+
+#[rustler::nif]
+fn append_path(image: ImageArc, path: PathArc) -> ImageResult {
+    {
+        let (image, path) = resource::lock_pair(&image, &path)?;
+        image.context.append_path(&path.path);
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn get_tolerance(image: ImageArc) -> f64 {
-    image.context.tolerance()
+fn get_tolerance(image: ImageArc) -> Result<f64, Error> {
+    Ok(image.lock()?.context.tolerance())
 }
 
 #[rustler::nif]
-fn set_tolerance(image: ImageArc, tolerance: f64) -> ImageArc {
-    image.context.set_tolerance(tolerance);
-    image
+fn set_tolerance(image: ImageArc, tolerance: f64) -> ImageResult {
+    image.lock()?.context.set_tolerance(tolerance);
+    Ok(image)
 }
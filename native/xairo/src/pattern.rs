@@ -0,0 +1,233 @@
+use crate::error::Error;
+use crate::linear_gradient::LinearGradientArc;
+use crate::mesh::MeshArc;
+use crate::radial_gradient::RadialGradientArc;
+use crate::raster_pattern::RasterPatternArc;
+use crate::resource::{self, Guarded};
+use crate::solid_pattern::SolidPatternArc;
+use crate::surface_pattern::SurfacePatternArc;
+use crate::xairo_image::{ImageArc, ImageResult, XairoImage};
+use rustler::{Decoder, Encoder, Env, NifResult, Term};
+
+/// Any pattern resource, so that NIFs which only need the underlying
+/// `cairo::Pattern` (e.g. to set it as a source) can accept every kind of
+/// pattern.
+pub enum PatternArc {
+    LinearGradient(LinearGradientArc),
+    RadialGradient(RadialGradientArc),
+    Mesh(MeshArc),
+    Solid(SolidPatternArc),
+    Surface(SurfacePatternArc),
+    Raster(RasterPatternArc),
+}
+
+/// The kind of a pattern resource.
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum PatternType {
+    LinearGradient,
+    RadialGradient,
+    Mesh,
+    Solid,
+    Surface,
+    Raster,
+}
+
+impl<'a> Decoder<'a> for PatternArc {
+    fn decode(term: Term<'a>) -> NifResult<Self> {
+        if let Ok(gradient) = term.decode() {
+            Ok(PatternArc::LinearGradient(gradient))
+        } else if let Ok(gradient) = term.decode() {
+            Ok(PatternArc::RadialGradient(gradient))
+        } else if let Ok(mesh) = term.decode() {
+            Ok(PatternArc::Mesh(mesh))
+        } else if let Ok(pattern) = term.decode() {
+            Ok(PatternArc::Solid(pattern))
+        } else if let Ok(pattern) = term.decode() {
+            Ok(PatternArc::Surface(pattern))
+        } else if let Ok(pattern) = term.decode() {
+            Ok(PatternArc::Raster(pattern))
+        } else {
+            Err(rustler::Error::BadArg)
+        }
+    }
+}
+
+impl Encoder for PatternArc {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self {
+            PatternArc::LinearGradient(gradient) => gradient.encode(env),
+            PatternArc::RadialGradient(gradient) => gradient.encode(env),
+            PatternArc::Mesh(mesh) => mesh.encode(env),
+            PatternArc::Solid(pattern) => pattern.encode(env),
+            PatternArc::Surface(pattern) => pattern.encode(env),
+            PatternArc::Raster(pattern) => pattern.encode(env),
+        }
+    }
+}
+
+impl PatternArc {
+    pub fn pattern_type(&self) -> PatternType {
+        match self {
+            PatternArc::LinearGradient(_) => PatternType::LinearGradient,
+            PatternArc::RadialGradient(_) => PatternType::RadialGradient,
+            PatternArc::Mesh(_) => PatternType::Mesh,
+            PatternArc::Solid(_) => PatternType::Solid,
+            PatternArc::Surface(_) => PatternType::Surface,
+            PatternArc::Raster(_) => PatternType::Raster,
+        }
+    }
+
+    /// Merges the pattern into the lock group of `image`, for as long as the
+    /// image's context may hold a reference to it.
+    pub fn join(&self, image: &Guarded<XairoImage>) -> Result<(), Error> {
+        match self {
+            PatternArc::LinearGradient(gradient) => resource::join(image, gradient),
+            PatternArc::RadialGradient(gradient) => resource::join(image, gradient),
+            PatternArc::Mesh(mesh) => resource::join(image, mesh),
+            PatternArc::Solid(pattern) => resource::join(image, pattern),
+            PatternArc::Surface(pattern) => resource::join(image, pattern),
+            PatternArc::Raster(pattern) => resource::join(image, pattern),
+        }
+    }
+
+    /// Locks `image` and the pattern together and calls `f` with the image's
+    /// context and the underlying cairo pattern.
+    pub fn with_image<T>(
+        &self,
+        image: &Guarded<XairoImage>,
+        f: impl FnOnce(&cairo::Context, &cairo::Pattern) -> T,
+    ) -> Result<T, Error> {
+        Ok(match self {
+            PatternArc::LinearGradient(gradient) => {
+                let (image, gradient) = resource::lock_pair(image, gradient)?;
+                f(&image.context, &gradient.gradient)
+            }
+            PatternArc::RadialGradient(gradient) => {
+                let (image, gradient) = resource::lock_pair(image, gradient)?;
+                f(&image.context, &gradient.gradient)
+            }
+            PatternArc::Mesh(mesh) => {
+                let (image, mesh) = resource::lock_pair(image, mesh)?;
+                f(&image.context, &mesh.mesh)
+            }
+            PatternArc::Solid(pattern) => {
+                let (image, pattern) = resource::lock_pair(image, pattern)?;
+                f(&image.context, &pattern.pattern)
+            }
+            PatternArc::Surface(pattern) => {
+                let (image, pattern) = resource::lock_pair(image, pattern)?;
+                f(&image.context, &pattern.pattern)
+            }
+            PatternArc::Raster(pattern) => {
+                let (image, pattern) = resource::lock_pair(image, pattern)?;
+                f(&image.context, &pattern.pattern)
+            }
+        })
+    }
+
+    fn source_error(&self) -> Error {
+        match self {
+            PatternArc::LinearGradient(_) => Error::SetSource("linear gradient"),
+            PatternArc::RadialGradient(_) => Error::SetSource("radial gradient"),
+            PatternArc::Mesh(_) => Error::SetSource("mesh"),
+            PatternArc::Solid(_) => Error::SetSource("solid pattern"),
+            PatternArc::Surface(_) => Error::SurfaceSource,
+            PatternArc::Raster(_) => Error::SetSource("raster pattern"),
+        }
+    }
+}
+
+/// How a pattern is drawn outside of its natural area, e.g. beyond the ends
+/// of a gradient or the edges of a surface.
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum Extend {
+    None,
+    Repeat,
+    Reflect,
+    Pad,
+}
+
+/// How a pattern's colors are sampled when it is drawn at a different size.
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum Filter {
+    Fast,
+    Good,
+    Best,
+    Nearest,
+    Bilinear,
+    Gaussian,
+}
+
+impl From<Extend> for cairo::Extend {
+    fn from(extend: Extend) -> Self {
+        match extend {
+            Extend::None => cairo::Extend::None,
+            Extend::Repeat => cairo::Extend::Repeat,
+            Extend::Reflect => cairo::Extend::Reflect,
+            Extend::Pad => cairo::Extend::Pad,
+        }
+    }
+}
+
+impl From<cairo::Extend> for Extend {
+    fn from(extend: cairo::Extend) -> Self {
+        match extend {
+            cairo::Extend::Repeat => Extend::Repeat,
+            cairo::Extend::Reflect => Extend::Reflect,
+            cairo::Extend::Pad => Extend::Pad,
+            _ => Extend::None,
+        }
+    }
+}
+
+impl From<Filter> for cairo::Filter {
+    fn from(filter: Filter) -> Self {
+        match filter {
+            Filter::Fast => cairo::Filter::Fast,
+            Filter::Good => cairo::Filter::Good,
+            Filter::Best => cairo::Filter::Best,
+            Filter::Nearest => cairo::Filter::Nearest,
+            Filter::Bilinear => cairo::Filter::Bilinear,
+            Filter::Gaussian => cairo::Filter::Gaussian,
+        }
+    }
+}
+
+impl From<cairo::Filter> for Filter {
+    fn from(filter: cairo::Filter) -> Self {
+        match filter {
+            cairo::Filter::Fast => Filter::Fast,
+            cairo::Filter::Best => Filter::Best,
+            cairo::Filter::Nearest => Filter::Nearest,
+            cairo::Filter::Bilinear => Filter::Bilinear,
+            cairo::Filter::Gaussian => Filter::Gaussian,
+            _ => Filter::Good,
+        }
+    }
+}
+
+#[rustler::nif]
+fn pattern_type(pattern: PatternArc) -> Result<PatternType, Error> {
+    Ok(pattern.pattern_type())
+}
+
+#[rustler::nif]
+fn set_source(image: ImageArc, pattern: PatternArc) -> ImageResult {
+    // The context keeps a reference to its source, so from now on drawing to
+    // the image also reads the pattern.
+    pattern.join(&image)?;
+    let result = pattern.with_image(&image, |context, source| context.set_source(source))?;
+    match result {
+        Ok(_) => Ok(image),
+        Err(_) => Err(pattern.source_error()),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn mask(image: ImageArc, pattern: PatternArc) -> ImageResult {
+    let result = pattern.with_image(&image, |context, mask| context.mask(mask))?;
+    match result {
+        Ok(_) => Ok(image),
+        Err(_) => Err(Error::MaskError),
+    }
+}
@@ -0,0 +1,167 @@
+use crate::color::Rgba;
+use crate::error::Error;
+use crate::packed;
+use crate::xairo_image::{ImageArc, ImageFormat, ImageResult, XairoSurface};
+use cairo::{Format, ImageSurface};
+use rustler::{Binary, Env};
+
+/// The raw pixel data of an image surface, as
+/// `{data, width, height, stride, format}`.
+pub type ImageData<'a> = (Binary<'a>, i32, i32, i32, ImageFormat);
+
+fn image_surface(surface: &XairoSurface) -> Result<&ImageSurface, Error> {
+    match surface {
+        XairoSurface::Image(surface) => Ok(surface),
+        _ => Err(Error::FileTypeMismatch),
+    }
+}
+
+/// Calls `f` with the surface's pixel data, flushing any pending drawing to
+/// the surface first.
+fn with_data<T>(surface: &ImageSurface, f: impl FnOnce(&[u8]) -> T) -> Result<T, Error> {
+    let mut result = None;
+    if surface.with_data(|data| result = Some(f(data))).is_err() {
+        return Err(Error::PixelAccess);
+    }
+    result.ok_or(Error::PixelAccess)
+}
+
+/// Reads the pixel at `x`, `y`, un-premultiplying its color by its alpha.
+///
+/// Formats without an alpha channel are read as opaque, and alpha-only
+/// formats as black with the stored alpha.
+fn read_pixel(
+    data: &[u8],
+    x: usize,
+    y: usize,
+    stride: usize,
+    format: Format,
+) -> Result<Rgba, Error> {
+    let row = &data[y * stride..];
+    match format {
+        Format::ARgb32 => {
+            let pixel = read_u32(row, x * 4);
+            Ok(unpremultiply(pixel >> 16, pixel >> 8, pixel, pixel >> 24))
+        }
+        Format::Rgb24 => {
+            let pixel = read_u32(row, x * 4);
+            Ok(unpremultiply(pixel >> 16, pixel >> 8, pixel, 0xff))
+        }
+        Format::A8 => Ok(unpremultiply(0, 0, 0, row[x] as u32)),
+        Format::A1 => {
+            // Pixels are packed into native-endian 32-bit words, starting
+            // from the least significant bit on little-endian machines and
+            // the most significant bit on big-endian ones.
+            let word = read_u32(row, x / 32 * 4);
+            let bit = if cfg!(target_endian = "little") {
+                x % 32
+            } else {
+                31 - x % 32
+            };
+            Ok(unpremultiply(0, 0, 0, ((word >> bit) & 1) * 0xff))
+        }
+        Format::Rgb16_565 => {
+            let mut bytes = [0; 2];
+            bytes.copy_from_slice(&row[x * 2..x * 2 + 2]);
+            let pixel = u16::from_ne_bytes(bytes) as u32;
+            Ok(opaque(pixel >> 11, 0x1f, pixel >> 5, 0x3f, pixel, 0x1f))
+        }
+        Format::Rgb30 => {
+            let pixel = read_u32(row, x * 4);
+            Ok(opaque(pixel >> 20, 0x3ff, pixel >> 10, 0x3ff, pixel, 0x3ff))
+        }
+        _ => Err(Error::UnsupportedFormat),
+    }
+}
+
+fn read_u32(row: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&row[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+/// Builds an opaque color from channels stored with the given bit masks.
+fn opaque(red: u32, red_max: u32, green: u32, green_max: u32, blue: u32, blue_max: u32) -> Rgba {
+    Rgba {
+        red: (red & red_max) as f64 / red_max as f64,
+        green: (green & green_max) as f64 / green_max as f64,
+        blue: (blue & blue_max) as f64 / blue_max as f64,
+        alpha: 1.0,
+    }
+}
+
+fn unpremultiply(red: u32, green: u32, blue: u32, alpha: u32) -> Rgba {
+    let alpha = alpha & 0xff;
+    if alpha == 0 {
+        return Rgba {
+            red: 0.0,
+            green: 0.0,
+            blue: 0.0,
+            alpha: 0.0,
+        };
+    }
+
+    let channel = |value: u32| ((value & 0xff) as f64 / alpha as f64).min(1.0);
+    Rgba {
+        red: channel(red),
+        green: channel(green),
+        blue: channel(blue),
+        alpha: alpha as f64 / 255.0,
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn image_data<'a>(env: Env<'a>, image: ImageArc) -> Result<ImageData<'a>, Error> {
+    let guard = image.lock()?;
+    let surface = image_surface(&guard.surface)?;
+    let data = with_data(surface, |data| packed::to_binary(env, data))??;
+
+    Ok((
+        data,
+        surface.width(),
+        surface.height(),
+        surface.stride(),
+        surface.format().into(),
+    ))
+}
+
+#[rustler::nif]
+fn get_pixel(image: ImageArc, x: i32, y: i32) -> Result<Rgba, Error> {
+    let guard = image.lock()?;
+    let surface = image_surface(&guard.surface)?;
+    if x < 0 || y < 0 || x >= surface.width() || y >= surface.height() {
+        return Err(Error::PixelOutOfBounds(x, y));
+    }
+
+    let (stride, format) = (surface.stride() as usize, surface.format());
+    with_data(surface, |data| {
+        read_pixel(data, x as usize, y as usize, stride, format)
+    })?
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn set_image_data(image: ImageArc, data: Binary) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        let surface = image_surface(&guard.surface)?;
+        let size = surface.stride() as usize * surface.height() as usize;
+        if data.len() != size {
+            return Err(Error::PixelData(data.len(), size));
+        }
+
+        surface.flush();
+        // SAFETY: the surface is flushed and the image lock is held, so
+        // nothing else reads or writes the pixel buffer while it is copied,
+        // and the buffer holds exactly `stride * height` bytes.
+        unsafe {
+            let buffer = cairo::ffi::cairo_image_surface_get_data(surface.to_raw_none());
+            if buffer.is_null() {
+                return Err(Error::PixelAccess);
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr(), buffer, size);
+        }
+        surface.mark_dirty();
+    }
+
+    Ok(image)
+}
@@ -1,17 +1,21 @@
 use crate::color::Rgba;
 use crate::error::Error;
+use crate::gradient::{self, ColorStop};
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
+use crate::resource::{Guardable, Guarded};
 use crate::shapes::Point;
-use crate::xairo_image::{ImageArc, ImageResult};
 use rustler::ResourceArc;
 
 pub struct XairoRadialGradient {
     pub gradient: cairo::RadialGradient,
 }
 
-unsafe impl Send for XairoRadialGradient {}
-unsafe impl Sync for XairoRadialGradient {}
+// SAFETY: the pattern is only shared with the contexts it is set on as a
+// source, and `set_source` joins each of those images into its lock group.
+unsafe impl Guardable for XairoRadialGradient {}
 
-pub type RadialGradientArc = ResourceArc<XairoRadialGradient>;
+pub type RadialGradientArc = ResourceArc<Guarded<XairoRadialGradient>>;
 
 #[rustler::nif]
 fn radial_gradient_new(
@@ -19,12 +23,12 @@ fn radial_gradient_new(
     radius1: f64,
     point2: Point,
     radius2: f64,
-) -> RadialGradientArc {
-    ResourceArc::new(XairoRadialGradient {
+) -> Result<RadialGradientArc, Error> {
+    Ok(ResourceArc::new(Guarded::new(XairoRadialGradient {
         gradient: cairo::RadialGradient::new(
             point1.x, point1.y, radius1, point2.x, point2.y, radius2,
         ),
-    })
+    })))
 }
 
 #[rustler::nif]
@@ -32,38 +36,57 @@ fn radial_gradient_add_color_stop(
     gradient: RadialGradientArc,
     offset: f64,
     color: Rgba,
-) -> RadialGradientArc {
+) -> Result<RadialGradientArc, Error> {
     let (r, g, b, a) = color.to_tuple();
-    gradient.gradient.add_color_stop_rgba(offset, r, g, b, a);
     gradient
+        .lock()?
+        .gradient
+        .add_color_stop_rgba(offset, r, g, b, a);
+    Ok(gradient)
 }
 
 #[rustler::nif]
 fn radial_gradient_color_stop_count(gradient: RadialGradientArc) -> Result<isize, Error> {
-    match gradient.gradient.color_stop_count() {
+    match gradient.lock()?.gradient.color_stop_count() {
         Ok(count) => Ok(count),
         Err(_) => Err(Error::ColorStopCount),
     }
 }
 
 #[rustler::nif]
-fn radial_gradient_color_stop(gradient: RadialGradientArc, index: isize) -> Result<Rgba, Error> {
-    match gradient.gradient.color_stop_rgba(index) {
-        Ok((_, red, green, blue, alpha)) => Ok(Rgba {
-            red,
-            green,
-            blue,
-            alpha,
-        }),
-        Err(_) => Err(Error::ColorStop(index)),
-    }
+fn radial_gradient_color_stop(
+    gradient: RadialGradientArc,
+    index: isize,
+) -> Result<ColorStop, Error> {
+    gradient::color_stop(&gradient.lock()?.gradient, index)
+}
+
+#[rustler::nif]
+fn radial_gradient_color_stops(gradient: RadialGradientArc) -> Result<Vec<ColorStop>, Error> {
+    gradient::color_stops(&gradient.lock()?.gradient)
+}
+
+#[rustler::nif]
+fn radial_gradient_set_color_stops(
+    gradient: RadialGradientArc,
+    stops: Vec<ColorStop>,
+) -> Result<RadialGradientArc, Error> {
+    let guard = gradient.lock()?;
+    let rebuilt = match guard.gradient.radial_circles() {
+        Ok((x1, y1, r1, x2, y2, r2)) => cairo::RadialGradient::new(x1, y1, r1, x2, y2, r2),
+        Err(_) => return Err(Error::RadialCircles),
+    };
+    gradient::copy_with_color_stops(&guard.gradient, &rebuilt, &stops);
+    Ok(ResourceArc::new(Guarded::new(XairoRadialGradient {
+        gradient: rebuilt,
+    })))
 }
 
 #[rustler::nif]
 fn radial_gradient_radial_circles(
     gradient: RadialGradientArc,
 ) -> Result<((Point, f64), (Point, f64)), Error> {
-    match gradient.gradient.radial_circles() {
+    match gradient.lock()?.gradient.radial_circles() {
         Ok((x1, y1, r1, x2, y2, r2)) => {
             let p1 = Point { x: x1, y: y1 };
             let p2 = Point { x: x2, y: y2 };
@@ -74,17 +97,44 @@ fn radial_gradient_radial_circles(
 }
 
 #[rustler::nif]
-fn set_radial_gradient_source(image: ImageArc, gradient: RadialGradientArc) -> ImageResult {
-    match image.context.set_source(&gradient.gradient) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::SetSource("radial gradient")),
-    }
+fn radial_gradient_set_matrix(
+    gradient: RadialGradientArc,
+    matrix: Matrix,
+) -> Result<RadialGradientArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    gradient.lock()?.gradient.set_matrix(matrix);
+    Ok(gradient)
 }
 
 #[rustler::nif]
-fn set_radial_gradient_mask(image: ImageArc, gradient: RadialGradientArc) -> ImageResult {
-    match image.context.mask(&gradient.gradient) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::MaskError),
-    }
+fn radial_gradient_matrix(gradient: RadialGradientArc) -> Result<Matrix, Error> {
+    Ok(gradient.lock()?.gradient.matrix().into())
+}
+
+#[rustler::nif]
+fn radial_gradient_set_extend(
+    gradient: RadialGradientArc,
+    extend: Extend,
+) -> Result<RadialGradientArc, Error> {
+    gradient.lock()?.gradient.set_extend(extend.into());
+    Ok(gradient)
+}
+
+#[rustler::nif]
+fn radial_gradient_extend(gradient: RadialGradientArc) -> Result<Extend, Error> {
+    Ok(gradient.lock()?.gradient.extend().into())
+}
+
+#[rustler::nif]
+fn radial_gradient_set_filter(
+    gradient: RadialGradientArc,
+    filter: Filter,
+) -> Result<RadialGradientArc, Error> {
+    gradient.lock()?.gradient.set_filter(filter.into());
+    Ok(gradient)
+}
+
+#[rustler::nif]
+fn radial_gradient_filter(gradient: RadialGradientArc) -> Result<Filter, Error> {
+    Ok(gradient.lock()?.gradient.filter().into())
 }
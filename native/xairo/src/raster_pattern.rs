@@ -0,0 +1,109 @@
+use crate::error::Error;
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
+use crate::resource::{Guardable, Guarded};
+use cairo::{Format, ImageSurface};
+use rustler::{Binary, ResourceArc};
+
+pub struct XairoRasterPattern {
+    pub pattern: cairo::SurfacePattern,
+}
+
+// SAFETY: the pattern owns the only reference to its surface, which is never
+// modified after it is created. The pattern itself is shared with the contexts
+// it is set on as a source, and `set_source` joins each of those into its
+// lock group.
+unsafe impl Guardable for XairoRasterPattern {}
+
+pub type RasterPatternArc = ResourceArc<Guarded<XairoRasterPattern>>;
+
+/// Converts rows of straight (not premultiplied) RGBA bytes into cairo's
+/// native-endian, premultiplied ARGB32 layout, padding each row to `stride`.
+fn argb32(data: &[u8], width: usize, height: usize, stride: usize) -> Vec<u8> {
+    let mut pixels = vec![0; stride * height];
+    for (y, row) in data.chunks_exact(width * 4).enumerate() {
+        for (x, rgba) in row.chunks_exact(4).enumerate() {
+            let alpha = rgba[3] as u32;
+            let premultiply = |channel: u8| (channel as u32 * alpha + 127) / 255;
+            let pixel = alpha << 24
+                | premultiply(rgba[0]) << 16
+                | premultiply(rgba[1]) << 8
+                | premultiply(rgba[2]);
+            let offset = y * stride + x * 4;
+            pixels[offset..offset + 4].copy_from_slice(&pixel.to_ne_bytes());
+        }
+    }
+    pixels
+}
+
+/// Creates a pattern from `width * height` pixels of straight RGBA bytes,
+/// given row by row from the top left.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn raster_pattern_new(width: i32, height: i32, data: Binary) -> Result<RasterPatternArc, Error> {
+    if width <= 0 || height <= 0 {
+        return Err(Error::SurfaceCreate);
+    }
+    let size = width as usize * height as usize * 4;
+    if data.len() != size {
+        return Err(Error::PixelData(data.len(), size));
+    }
+
+    let stride = Format::ARgb32
+        .stride_for_width(width as u32)
+        .map_err(|_| Error::SurfaceCreate)?;
+    let pixels = argb32(
+        data.as_slice(),
+        width as usize,
+        height as usize,
+        stride as usize,
+    );
+    let surface = ImageSurface::create_for_data(pixels, Format::ARgb32, width, height, stride)
+        .map_err(|_| Error::SurfaceCreate)?;
+
+    Ok(ResourceArc::new(Guarded::new(XairoRasterPattern {
+        pattern: cairo::SurfacePattern::create(&surface),
+    })))
+}
+
+#[rustler::nif]
+fn raster_pattern_set_matrix(
+    pattern: RasterPatternArc,
+    matrix: Matrix,
+) -> Result<RasterPatternArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    pattern.lock()?.pattern.set_matrix(matrix);
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn raster_pattern_matrix(pattern: RasterPatternArc) -> Result<Matrix, Error> {
+    Ok(pattern.lock()?.pattern.matrix().into())
+}
+
+#[rustler::nif]
+fn raster_pattern_set_extend(
+    pattern: RasterPatternArc,
+    extend: Extend,
+) -> Result<RasterPatternArc, Error> {
+    pattern.lock()?.pattern.set_extend(extend.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn raster_pattern_extend(pattern: RasterPatternArc) -> Result<Extend, Error> {
+    Ok(pattern.lock()?.pattern.extend().into())
+}
+
+#[rustler::nif]
+fn raster_pattern_set_filter(
+    pattern: RasterPatternArc,
+    filter: Filter,
+) -> Result<RasterPatternArc, Error> {
+    pattern.lock()?.pattern.set_filter(filter.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn raster_pattern_filter(pattern: RasterPatternArc) -> Result<Filter, Error> {
+    Ok(pattern.lock()?.pattern.filter().into())
+}
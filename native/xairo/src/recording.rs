@@ -0,0 +1,66 @@
+use crate::error::Error;
+use crate::resource;
+use crate::shapes::{Point, Rectangle};
+use crate::xairo_image::{ImageArc, ImageResult, XairoSurface};
+use cairo::{Context, Surface};
+
+/// Paints `surface` onto `context` with its origin at `origin` and scaled by
+/// `sx`, `sy`, leaving the context's state unchanged.
+fn paint_surface(
+    context: &Context,
+    surface: &Surface,
+    origin: Point,
+    sx: f64,
+    sy: f64,
+) -> Result<(), Error> {
+    if context.save().is_err() {
+        return Err(Error::ContextState("save"));
+    }
+
+    context.translate(origin.x, origin.y);
+    context.scale(sx, sy);
+    let result = context
+        .set_source_surface(surface, 0.0, 0.0)
+        .and_then(|_| context.paint());
+
+    if context.restore().is_err() {
+        return Err(Error::ContextState("restore"));
+    }
+    result.map_err(|_| Error::PathRender("replay"))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn replay(image: ImageArc, recording: ImageArc, origin: Point, sx: f64, sy: f64) -> ImageResult {
+    // Vector surfaces keep a reference to the surfaces painted onto them, so
+    // from now on saving the image also reads the recording.
+    resource::join(&image, &recording)?;
+    let result = {
+        let (target, recording) = resource::lock_pair(&image, &recording)?;
+        match &recording.surface {
+            XairoSurface::Recording(surface) => {
+                paint_surface(&target.context, surface, origin, sx, sy)
+            }
+            _ => Err(Error::FileTypeMismatch),
+        }
+    };
+
+    match result {
+        Ok(_) => Ok(image),
+        Err(e) => Err(e),
+    }
+}
+
+#[rustler::nif]
+fn ink_extents(recording: ImageArc) -> Result<Rectangle, Error> {
+    match &recording.lock()?.surface {
+        XairoSurface::Recording(surface) => {
+            let (x, y, width, height) = surface.ink_extents();
+            Ok(Rectangle {
+                corner: Point { x, y },
+                width,
+                height,
+            })
+        }
+        _ => Err(Error::FileTypeMismatch),
+    }
+}
@@ -0,0 +1,304 @@
+use crate::error::Error;
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+struct State<T> {
+    value: T,
+    finished: bool,
+}
+
+/// Marks a type that may be stored in a `Guarded` resource, and so moved to
+/// and used from any scheduler thread, one thread at a time.
+///
+/// # Safety
+///
+/// cairo objects are not thread safe, but only their shared, unsynchronized
+/// state makes them unsafe to use from another thread; their reference counts
+/// are atomic. Implementors must only hold objects whose state is reached
+/// through this resource or through resources in the same lock group, either
+/// because they are created in it (`Guarded::sharing`) or because their
+/// groups are merged when the state becomes shared (`join`).
+pub unsafe trait Guardable {}
+
+/// Wraps a cairo object stored in a NIF resource so that it can only be
+/// reached through a lock.
+///
+/// Resources can be shared between any number of BEAM processes, which may
+/// call into the NIF from different scheduler threads at the same time, while
+/// cairo objects are not safe to use from more than one thread at once.
+/// Guarding each resource with its own mutex serializes those calls.
+///
+/// Resources whose cairo objects share state (e.g. a sub-surface drawing into
+/// its parent's surface) are created in the same group, and locking any
+/// resource also locks its group, so that they are serialized together.
+/// Resources that start sharing state later (e.g. a pattern set as an image's
+/// source) have their groups merged.
+///
+/// Locking waits until every other call into the group has returned, which
+/// may take as long as the slowest drawing operation. NIFs that run on normal
+/// schedulers are therefore run through `without_waiting`, so that they are
+/// rescheduled onto a dirty scheduler instead of waiting (see `Yielding`).
+pub struct Guarded<T> {
+    state: Mutex<State<T>>,
+    group: Arc<Group>,
+}
+
+// The wrapped value is only ever accessed while holding the resource's lock
+// and its group's lock, and `Guardable` guarantees that nothing outside the
+// group reaches its cairo state.
+unsafe impl<T: Guardable> Send for Guarded<T> {}
+unsafe impl<T: Guardable> Sync for Guarded<T> {}
+
+/// A lock shared by resources whose cairo objects share state.
+///
+/// When two groups are merged, one is pointed at the other, and locking it
+/// from then on locks the group it was merged into instead.
+struct Group {
+    state: Mutex<GroupState>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct GroupState {
+    locked: bool,
+    merged_into: Option<Arc<Group>>,
+}
+
+/// Holds a group locked until it is dropped.
+struct GroupLock {
+    group: Arc<Group>,
+}
+
+impl Group {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(GroupState::default()),
+            released: Condvar::new(),
+        })
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, GroupState>, Error> {
+        self.state.lock().map_err(|_| Error::ResourceLock)
+    }
+
+    /// Follows any merges to the group that currently stands for this one.
+    fn root(self: &Arc<Self>) -> Result<Arc<Group>, Error> {
+        let mut group = self.clone();
+        loop {
+            let merged_into = group.state()?.merged_into.clone();
+            match merged_into {
+                Some(next) => group = next,
+                None => return Ok(group),
+            }
+        }
+    }
+
+    /// Waits until the group is released and locks it, or returns `None` if it
+    /// is merged into another group in the meantime. If `wait` is false, fails
+    /// with `Error::ResourceBusy` instead of waiting.
+    fn lock_root(self: &Arc<Self>, wait: bool) -> Result<Option<GroupLock>, Error> {
+        let mut state = self.state()?;
+        loop {
+            if state.merged_into.is_some() {
+                return Ok(None);
+            }
+            if !state.locked {
+                state.locked = true;
+                return Ok(Some(GroupLock {
+                    group: self.clone(),
+                }));
+            }
+            if !wait {
+                return Err(Error::ResourceBusy);
+            }
+            state = self.released.wait(state).map_err(|_| Error::ResourceLock)?;
+        }
+    }
+}
+
+impl Drop for GroupLock {
+    fn drop(&mut self) {
+        let mut state = match self.group.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.locked = false;
+        drop(state);
+        self.group.released.notify_all();
+    }
+}
+
+thread_local! {
+    /// Whether the next lock taken on this thread may wait for a busy group.
+    static MAY_WAIT: Cell<bool> = const { Cell::new(true) };
+    /// Whether a lock on this thread failed because its group was busy.
+    static WAS_BUSY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, the body of a NIF, so that the first lock it takes fails with
+/// `Error::ResourceBusy` instead of waiting if its group is locked, and
+/// returns whether it did alongside the result of `f`.
+///
+/// Only the first lock can fail: once it is taken, later locks wait as
+/// usual, so that if `f` was busy it failed before doing anything, and it can
+/// safely be run again from a thread that may wait.
+pub fn without_waiting<R>(f: impl FnOnce() -> R) -> (R, bool) {
+    MAY_WAIT.with(|may_wait| may_wait.set(false));
+    WAS_BUSY.with(|was_busy| was_busy.set(false));
+    let result = f();
+    MAY_WAIT.with(|may_wait| may_wait.set(true));
+    (result, WAS_BUSY.with(Cell::get))
+}
+
+/// Locks every distinct group among `groups`, always in the same order (by
+/// address), so that two processes locking overlapping groups cannot
+/// deadlock. If a group is merged while waiting for it, every lock is
+/// released and the groups are looked up again.
+fn lock_groups(groups: &[&Arc<Group>]) -> Result<Vec<GroupLock>, Error> {
+    let wait = MAY_WAIT.with(|may_wait| may_wait.replace(true));
+    'retry: loop {
+        let mut roots = groups
+            .iter()
+            .map(|group| group.root())
+            .collect::<Result<Vec<_>, _>>()?;
+        roots.sort_by_key(Arc::as_ptr);
+        roots.dedup_by(|a, b| Arc::ptr_eq(a, b));
+
+        let mut locks = Vec::with_capacity(roots.len());
+        for root in &roots {
+            match root.lock_root(wait) {
+                Ok(Some(lock)) => locks.push(lock),
+                Ok(None) => continue 'retry,
+                Err(Error::ResourceBusy) => {
+                    WAS_BUSY.with(|was_busy| was_busy.set(true));
+                    return Err(Error::ResourceBusy);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        return Ok(locks);
+    }
+}
+
+pub struct Guard<'a, T> {
+    state: MutexGuard<'a, State<T>>,
+    _groups: Vec<GroupLock>,
+}
+
+impl<T> Guarded<T> {
+    pub fn new(value: T) -> Self {
+        Self::in_group(value, Group::new())
+    }
+
+    /// Wraps a value whose cairo object shares state with `other`, placing it
+    /// in the same lock group.
+    pub fn sharing<U>(value: T, other: &Guarded<U>) -> Self {
+        Self::in_group(value, other.group.clone())
+    }
+
+    fn in_group(value: T, group: Arc<Group>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                value,
+                finished: false,
+            }),
+            group,
+        }
+    }
+
+    /// Blocks until the resource is available and returns a guard giving
+    /// access to the wrapped value, or an error if the resource has been
+    /// finished.
+    pub fn lock(&self) -> Result<Guard<'_, T>, Error> {
+        let groups = lock_groups(&[&self.group])?;
+        self.lock_in_group(groups)
+    }
+
+    /// Like `lock`, but also gives access to a finished resource, returning
+    /// whether it has been finished alongside the guard.
+    pub fn lock_any(&self) -> Result<(Guard<'_, T>, bool), Error> {
+        let groups = lock_groups(&[&self.group])?;
+        self.lock_state(groups)
+    }
+
+    fn lock_state(&self, groups: Vec<GroupLock>) -> Result<(Guard<'_, T>, bool), Error> {
+        match self.state.lock() {
+            Ok(state) => {
+                let finished = state.finished;
+                Ok((
+                    Guard {
+                        state,
+                        _groups: groups,
+                    },
+                    finished,
+                ))
+            }
+            Err(_) => Err(Error::ResourceLock),
+        }
+    }
+
+    /// Locks the resource while its group is already locked by the caller.
+    fn lock_in_group(&self, groups: Vec<GroupLock>) -> Result<Guard<'_, T>, Error> {
+        match self.lock_state(groups)? {
+            (_, true) => Err(Error::ResourceFinished),
+            (guard, false) => Ok(guard),
+        }
+    }
+}
+
+impl<'a, T> Guard<'a, T> {
+    /// Marks the resource as finished, so that every subsequent attempt to
+    /// lock it returns an error.
+    pub fn finish(&mut self) {
+        self.state.finished = true;
+    }
+}
+
+impl<'a, T> Deref for Guard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.state.value
+    }
+}
+
+impl<'a, T> DerefMut for Guard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.state.value
+    }
+}
+
+/// Locks two resources together, locking their groups in a consistent order
+/// so that two processes locking the same pair in opposite orders cannot
+/// deadlock. Resources in the same group are locked under a single lock of
+/// the group.
+pub fn lock_pair<'a, T, U>(
+    first: &'a Guarded<T>,
+    second: &'a Guarded<U>,
+) -> Result<(Guard<'a, T>, Guard<'a, U>), Error> {
+    if std::ptr::eq(
+        first as *const Guarded<T> as *const (),
+        second as *const Guarded<U> as *const (),
+    ) {
+        return Err(Error::SameResource);
+    }
+
+    let groups = lock_groups(&[&first.group, &second.group])?;
+    let first = first.lock_in_group(groups)?;
+    let second = second.lock_in_group(Vec::new())?;
+    Ok((first, second))
+}
+
+/// Merges the lock groups of two resources whose cairo objects have started
+/// sharing state, so that from then on locking either one locks both.
+///
+/// Groups are never split again, even if the objects stop sharing state, so
+/// this can only ever serialize more calls than needed, never fewer.
+pub fn join<T, U>(first: &Guarded<T>, second: &Guarded<U>) -> Result<(), Error> {
+    let locks = lock_groups(&[&first.group, &second.group])?;
+    if let [into, merged] = locks.as_slice() {
+        merged.group.state()?.merged_into = Some(into.group.clone());
+    }
+    Ok(())
+}
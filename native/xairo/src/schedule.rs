@@ -0,0 +1,52 @@
+use crate::resource;
+use rustler::codegen_runtime::{c_int, NifReturned, DEF_NIF_FUNC, NIF_ENV, NIF_TERM};
+use rustler::{Env, Nif, SchedulerFlags};
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+
+/// Registers the NIF `N`, which runs on a normal scheduler, so that it never
+/// waits for a resource locked by another process.
+///
+/// The NIF is first run without waiting for locks (see
+/// `resource::without_waiting`). If its resource was busy, it is rescheduled
+/// onto a dirty CPU scheduler with the same arguments, where it waits for the
+/// lock without blocking a normal scheduler.
+///
+/// Used in `rustler::init!` in place of the NIF, e.g. `Yielding::<move_to>`.
+pub struct Yielding<N>(PhantomData<N>);
+
+impl<N: Nif> Nif for Yielding<N> {
+    const NAME: *const u8 = N::NAME;
+    const ARITY: u32 = N::ARITY;
+    const FLAGS: u32 = SchedulerFlags::Normal as u32;
+    const RAW_FUNC: unsafe extern "C" fn(NIF_ENV, c_int, *const NIF_TERM) -> NIF_TERM =
+        yielding::<N>;
+    const FUNC: DEF_NIF_FUNC = DEF_NIF_FUNC {
+        arity: Self::ARITY,
+        flags: Self::FLAGS,
+        function: Self::RAW_FUNC,
+        name: Self::NAME,
+    };
+}
+
+unsafe extern "C" fn yielding<N: Nif>(
+    nif_env: NIF_ENV,
+    argc: c_int,
+    argv: *const NIF_TERM,
+) -> NIF_TERM {
+    let (result, busy) = resource::without_waiting(|| N::RAW_FUNC(nif_env, argc, argv));
+    if !busy {
+        return result;
+    }
+
+    let lifetime = ();
+    let env = Env::new(&lifetime, nif_env);
+    NifReturned::Reschedule {
+        fun_name: CStr::from_ptr(N::NAME as *const c_char).to_owned(),
+        flags: SchedulerFlags::DirtyCpu,
+        fun: N::RAW_FUNC,
+        args: std::slice::from_raw_parts(argv, argc as usize).to_vec(),
+    }
+    .apply(env)
+}
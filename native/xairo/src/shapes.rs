@@ -1,4 +1,4 @@
-use crate::xairo_image::ImageArc;
+use crate::xairo_image::{ImageArc, ImageResult};
 
 #[derive(Copy, Clone, Debug, NifStruct)]
 #[module = "Xairo.Point"]
@@ -72,13 +72,16 @@ pub struct Dashes {
 }
 
 #[rustler::nif]
-fn set_dash(image: ImageArc, dashes: Dashes) -> ImageArc {
-    image.context.set_dash(&dashes.dashes, dashes.offset);
+fn set_dash(image: ImageArc, dashes: Dashes) -> ImageResult {
     image
+        .lock()?
+        .context
+        .set_dash(&dashes.dashes, dashes.offset);
+    Ok(image)
 }
 
 #[rustler::nif]
-fn set_line_width(image: ImageArc, line_width: f64) -> ImageArc {
-    image.context.set_line_width(line_width);
-    image
+fn set_line_width(image: ImageArc, line_width: f64) -> ImageResult {
+    image.lock()?.context.set_line_width(line_width);
+    Ok(image)
 }
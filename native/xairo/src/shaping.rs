@@ -0,0 +1,276 @@
+use crate::error::Error;
+use crate::resource::{self, Guardable, Guarded};
+use crate::xairo_image::{ImageArc, ImageResult};
+use freetype::freetype::{
+    FT_Done_Face, FT_Done_FreeType, FT_Face, FT_Init_FreeType, FT_Library, FT_Long,
+    FT_New_Memory_Face,
+};
+use rustler::ResourceArc;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub struct XairoFontFace {
+    pub font_face: cairo::FontFace,
+    pub data: Arc<Vec<u8>>,
+}
+
+// SAFETY: the font face and its data are never modified after the face is
+// loaded. The FreeType library and face it renders with are owned by the
+// cairo font face itself (see `FtFace`), so they live exactly as long as any
+// context the face is set on, and cairo locks the FreeType face whenever it
+// renders with it, so the face can be set on contexts used from any thread.
+unsafe impl Guardable for XairoFontFace {}
+
+/// A FreeType face loaded from memory, along with the library that loaded it
+/// and the font data it reads from, none of which may be freed before the
+/// cairo font face created from it.
+///
+/// It is attached to that cairo font face as user data, so that cairo drops
+/// it when the font face is destroyed, rather than when the resource is.
+struct FtFace {
+    library: FT_Library,
+    face: FT_Face,
+    _data: Arc<Vec<u8>>,
+}
+
+static FT_FACE_KEY: cairo::UserDataKey<FtFace> = cairo::UserDataKey::new();
+
+impl FtFace {
+    fn new(data: Arc<Vec<u8>>) -> Result<Self, Error> {
+        let mut library = ptr::null_mut();
+        if !freetype::succeeded(unsafe { FT_Init_FreeType(&mut library) }) {
+            return Err(Error::TextShaping);
+        }
+
+        let mut face = ptr::null_mut();
+        let loaded = unsafe {
+            FT_New_Memory_Face(library, data.as_ptr(), data.len() as FT_Long, 0, &mut face)
+        };
+        if !freetype::succeeded(loaded) {
+            unsafe { FT_Done_FreeType(library) };
+            return Err(Error::TextShaping);
+        }
+
+        Ok(Self {
+            library,
+            face,
+            _data: data,
+        })
+    }
+}
+
+impl Drop for FtFace {
+    fn drop(&mut self) {
+        unsafe {
+            FT_Done_Face(self.face);
+            FT_Done_FreeType(self.library);
+        }
+    }
+}
+
+pub type FontFaceArc = ResourceArc<Guarded<XairoFontFace>>;
+
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+    Ttb,
+    Btt,
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "Xairo.Text.ShapingOptions"]
+pub struct ShapingOptions {
+    pub direction: Option<TextDirection>,
+    pub script: Option<String>,
+    pub language: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, NifStruct)]
+#[module = "Xairo.Text.Glyph"]
+pub struct Glyph {
+    pub index: u32,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl From<Glyph> for cairo::Glyph {
+    fn from(glyph: Glyph) -> Self {
+        cairo::Glyph {
+            index: glyph.index as _,
+            x: glyph.x,
+            y: glyph.y,
+        }
+    }
+}
+
+#[rustler::nif]
+fn font_face_from_file(filename: String) -> Result<FontFaceArc, Error> {
+    let data = match std::fs::read(&filename) {
+        Ok(data) => Arc::new(data),
+        Err(_) => return Err(Error::FontLoad(filename)),
+    };
+
+    if rustybuzz::Face::from_slice(&data, 0).is_none() {
+        return Err(Error::FontLoad(filename));
+    }
+
+    match load_ft_font_face(data.clone()) {
+        Ok(font_face) => Ok(ResourceArc::new(Guarded::new(XairoFontFace {
+            font_face,
+            data,
+        }))),
+        Err(_) => Err(Error::FontLoad(filename)),
+    }
+}
+
+#[rustler::nif]
+fn set_loaded_font_face(image: ImageArc, font_face: FontFaceArc) -> ImageResult {
+    {
+        let (image, font_face) = resource::lock_pair(&image, &font_face)?;
+        image.context.set_font_face(&font_face.font_face);
+    }
+    Ok(image)
+}
+
+/// A glyph shaped by rustybuzz, with its offsets and advances scaled to
+/// userspace.
+pub struct ShapedGlyph {
+    pub index: u32,
+    /// The byte offset in the shaped text of the cluster the glyph belongs
+    /// to. Glyphs that share a cluster (e.g. a base and its combining marks)
+    /// are consecutive.
+    pub cluster: u32,
+    pub x_offset: f64,
+    pub y_offset: f64,
+    pub x_advance: f64,
+    pub y_advance: f64,
+}
+
+/// Shapes `text` with `font_face`, at the size of the font set on `context`,
+/// returning the glyphs in visual order.
+pub fn shape(
+    font_face: &XairoFontFace,
+    context: &cairo::Context,
+    text: &str,
+    options: ShapingOptions,
+) -> Result<Vec<ShapedGlyph>, Error> {
+    let face = match rustybuzz::Face::from_slice(&font_face.data, 0) {
+        Some(face) => face,
+        None => return Err(Error::TextShaping),
+    };
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(text);
+
+    if let Some(direction) = options.direction {
+        buffer.set_direction(match_text_direction(direction));
+    }
+    if let Some(script) = options.script {
+        match script.parse::<rustybuzz::Script>() {
+            Ok(script) => buffer.set_script(script),
+            Err(_) => return Err(Error::InvalidScript(script)),
+        }
+    }
+    if let Some(language) = options.language {
+        match language.parse::<rustybuzz::Language>() {
+            Ok(language) => buffer.set_language(language),
+            Err(_) => return Err(Error::InvalidLanguage(language)),
+        }
+    }
+
+    let glyph_buffer = rustybuzz::shape(&face, &[], buffer);
+
+    // glyph positions are returned in font units with the y-axis pointing up,
+    // so they are scaled by the context's font matrix and flipped to match
+    // cairo's userspace
+    let font_matrix = context.font_matrix();
+    let units_per_em = face.units_per_em() as f64;
+    let x_scale = font_matrix.xx / units_per_em;
+    let y_scale = font_matrix.yy / units_per_em;
+
+    Ok(glyph_buffer
+        .glyph_infos()
+        .iter()
+        .zip(glyph_buffer.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            index: info.glyph_id,
+            cluster: info.cluster,
+            x_offset: position.x_offset as f64 * x_scale,
+            y_offset: -position.y_offset as f64 * y_scale,
+            x_advance: position.x_advance as f64 * x_scale,
+            y_advance: -position.y_advance as f64 * y_scale,
+        })
+        .collect())
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn shape_text(
+    image: ImageArc,
+    font_face: FontFaceArc,
+    text: &str,
+    options: ShapingOptions,
+) -> Result<Vec<Glyph>, Error> {
+    let (guard, font_face) = resource::lock_pair(&image, &font_face)?;
+    let shaped = shape(&font_face, &guard.context, text, options)?;
+
+    let (mut x, mut y) = match guard.context.has_current_point() {
+        Ok(true) => guard.context.current_point().unwrap_or((0.0, 0.0)),
+        _ => (0.0, 0.0),
+    };
+
+    let glyphs = shaped
+        .iter()
+        .map(|shaped| {
+            let glyph = Glyph {
+                index: shaped.index,
+                x: x + shaped.x_offset,
+                y: y + shaped.y_offset,
+            };
+            x += shaped.x_advance;
+            y += shaped.y_advance;
+            glyph
+        })
+        .collect();
+
+    Ok(glyphs)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn show_glyphs(image: ImageArc, glyphs: Vec<Glyph>) -> ImageResult {
+    let glyphs: Vec<cairo::Glyph> = glyphs.into_iter().map(cairo::Glyph::from).collect();
+    let result = image.lock()?.context.show_glyphs(&glyphs);
+    match result {
+        Ok(_) => Ok(image),
+        Err(_) => Err(Error::PathRender("show_glyphs")),
+    }
+}
+
+#[rustler::nif]
+fn glyph_path(image: ImageArc, glyphs: Vec<Glyph>) -> ImageResult {
+    let glyphs: Vec<cairo::Glyph> = glyphs.into_iter().map(cairo::Glyph::from).collect();
+    image.lock()?.context.glyph_path(&glyphs);
+    Ok(image)
+}
+
+fn load_ft_font_face(data: Arc<Vec<u8>>) -> Result<cairo::FontFace, Error> {
+    let ft_face = FtFace::new(data)?;
+    // SAFETY: the FreeType face is attached to the font face below, so it is
+    // only freed once cairo destroys the font face.
+    let font_face =
+        unsafe { cairo::FontFace::create_from_ft(ft_face.face) }.map_err(|_| Error::TextShaping)?;
+    font_face
+        .set_user_data(&FT_FACE_KEY, Rc::new(ft_face))
+        .map_err(|_| Error::TextShaping)?;
+    Ok(font_face)
+}
+
+fn match_text_direction(direction: TextDirection) -> rustybuzz::Direction {
+    match direction {
+        TextDirection::Ltr => rustybuzz::Direction::LeftToRight,
+        TextDirection::Rtl => rustybuzz::Direction::RightToLeft,
+        TextDirection::Ttb => rustybuzz::Direction::TopToBottom,
+        TextDirection::Btt => rustybuzz::Direction::BottomToTop,
+    }
+}
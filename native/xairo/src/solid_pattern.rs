@@ -1,28 +1,31 @@
 use crate::color::Rgba;
 use crate::error::Error;
-use crate::xairo_image::{ImageArc, ImageResult};
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
+use crate::resource::{Guardable, Guarded};
 use rustler::ResourceArc;
 
 pub struct XairoSolidPattern {
     pub pattern: cairo::SolidPattern,
 }
 
-unsafe impl Send for XairoSolidPattern {}
-unsafe impl Sync for XairoSolidPattern {}
+// SAFETY: the pattern is only shared with the contexts it is set on as a
+// source, and `set_source` joins each of those images into its lock group.
+unsafe impl Guardable for XairoSolidPattern {}
 
-pub type SolidPatternArc = ResourceArc<XairoSolidPattern>;
+pub type SolidPatternArc = ResourceArc<Guarded<XairoSolidPattern>>;
 
 #[rustler::nif]
-fn solid_pattern_from_rgba(color: Rgba) -> SolidPatternArc {
+fn solid_pattern_from_rgba(color: Rgba) -> Result<SolidPatternArc, Error> {
     let (r, g, b, a) = color.to_tuple();
-    ResourceArc::new(XairoSolidPattern {
+    Ok(ResourceArc::new(Guarded::new(XairoSolidPattern {
         pattern: cairo::SolidPattern::from_rgba(r, g, b, a),
-    })
+    })))
 }
 
 #[rustler::nif]
 fn solid_pattern_color(pattern: SolidPatternArc) -> Result<Rgba, Error> {
-    match pattern.pattern.rgba() {
+    match pattern.lock()?.pattern.rgba() {
         Ok((red, green, blue, alpha)) => Ok(Rgba {
             red,
             green,
@@ -34,17 +37,44 @@ fn solid_pattern_color(pattern: SolidPatternArc) -> Result<Rgba, Error> {
 }
 
 #[rustler::nif]
-fn set_solid_pattern_source(image: ImageArc, pattern: SolidPatternArc) -> ImageResult {
-    match image.context.set_source(&pattern.pattern) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::SetSource("solid pattern")),
-    }
+fn solid_pattern_set_matrix(
+    pattern: SolidPatternArc,
+    matrix: Matrix,
+) -> Result<SolidPatternArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    pattern.lock()?.pattern.set_matrix(matrix);
+    Ok(pattern)
 }
 
 #[rustler::nif]
-fn set_solid_pattern_mask(image: ImageArc, pattern: SolidPatternArc) -> ImageResult {
-    match image.context.mask(&pattern.pattern) {
-        Ok(_) => Ok(image),
-        Err(_) => Err(Error::MaskError),
-    }
+fn solid_pattern_matrix(pattern: SolidPatternArc) -> Result<Matrix, Error> {
+    Ok(pattern.lock()?.pattern.matrix().into())
+}
+
+#[rustler::nif]
+fn solid_pattern_set_extend(
+    pattern: SolidPatternArc,
+    extend: Extend,
+) -> Result<SolidPatternArc, Error> {
+    pattern.lock()?.pattern.set_extend(extend.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn solid_pattern_extend(pattern: SolidPatternArc) -> Result<Extend, Error> {
+    Ok(pattern.lock()?.pattern.extend().into())
+}
+
+#[rustler::nif]
+fn solid_pattern_set_filter(
+    pattern: SolidPatternArc,
+    filter: Filter,
+) -> Result<SolidPatternArc, Error> {
+    pattern.lock()?.pattern.set_filter(filter.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn solid_pattern_filter(pattern: SolidPatternArc) -> Result<Filter, Error> {
+    Ok(pattern.lock()?.pattern.filter().into())
 }
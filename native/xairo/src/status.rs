@@ -0,0 +1,126 @@
+use crate::error::Error;
+use crate::xairo_image::ImageArc;
+
+/// The status of a context, mirroring cairo's `cairo_status_t`.
+#[allow(clippy::enum_variant_names)]
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum Status {
+    Success,
+    NoMemory,
+    InvalidRestore,
+    InvalidPopGroup,
+    NoCurrentPoint,
+    InvalidMatrix,
+    InvalidStatus,
+    NullPointer,
+    InvalidString,
+    InvalidPathData,
+    ReadError,
+    WriteError,
+    SurfaceFinished,
+    SurfaceTypeMismatch,
+    PatternTypeMismatch,
+    InvalidContent,
+    InvalidFormat,
+    InvalidVisual,
+    FileNotFound,
+    InvalidDash,
+    InvalidDscComment,
+    InvalidIndex,
+    ClipNotRepresentable,
+    TempFileError,
+    InvalidStride,
+    FontTypeMismatch,
+    UserFontImmutable,
+    UserFontError,
+    NegativeCount,
+    InvalidClusters,
+    InvalidSlant,
+    InvalidWeight,
+    InvalidSize,
+    UserFontNotImplemented,
+    DeviceTypeMismatch,
+    DeviceError,
+    InvalidMeshConstruction,
+    DeviceFinished,
+    Jbig2GlobalMissing,
+    PngError,
+    FreetypeError,
+    Win32GdiError,
+    Unknown,
+}
+
+#[rustler::nif]
+fn status(image: ImageArc) -> Result<Status, Error> {
+    match image.lock()?.context.status() {
+        Ok(_) => Ok(Status::Success),
+        Err(error) => Ok(status_from_cairo(error)),
+    }
+}
+
+/// Returns an error carrying the context's status if it is in an error state.
+///
+/// Once a cairo context has an error status every subsequent operation on it
+/// is a no-op, so NIFs that modify the context should call this before
+/// releasing the image's lock, rather than assuming the operation succeeded.
+pub fn check(context: &cairo::Context) -> Result<(), Error> {
+    match context.status() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::InvalidStatus(status_from_cairo(error))),
+    }
+}
+
+/// Returns an error carrying the pattern's status if it is in an error state.
+pub fn check_pattern(pattern: &cairo::Pattern) -> Result<(), Error> {
+    match pattern.status() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::InvalidStatus(status_from_cairo(error))),
+    }
+}
+
+fn status_from_cairo(error: cairo::Error) -> Status {
+    match error {
+        cairo::Error::NoMemory => Status::NoMemory,
+        cairo::Error::InvalidRestore => Status::InvalidRestore,
+        cairo::Error::InvalidPopGroup => Status::InvalidPopGroup,
+        cairo::Error::NoCurrentPoint => Status::NoCurrentPoint,
+        cairo::Error::InvalidMatrix => Status::InvalidMatrix,
+        cairo::Error::InvalidStatus => Status::InvalidStatus,
+        cairo::Error::NullPointer => Status::NullPointer,
+        cairo::Error::InvalidString => Status::InvalidString,
+        cairo::Error::InvalidPathData => Status::InvalidPathData,
+        cairo::Error::ReadError => Status::ReadError,
+        cairo::Error::WriteError => Status::WriteError,
+        cairo::Error::SurfaceFinished => Status::SurfaceFinished,
+        cairo::Error::SurfaceTypeMismatch => Status::SurfaceTypeMismatch,
+        cairo::Error::PatternTypeMismatch => Status::PatternTypeMismatch,
+        cairo::Error::InvalidContent => Status::InvalidContent,
+        cairo::Error::InvalidFormat => Status::InvalidFormat,
+        cairo::Error::InvalidVisual => Status::InvalidVisual,
+        cairo::Error::FileNotFound => Status::FileNotFound,
+        cairo::Error::InvalidDash => Status::InvalidDash,
+        cairo::Error::InvalidDscComment => Status::InvalidDscComment,
+        cairo::Error::InvalidIndex => Status::InvalidIndex,
+        cairo::Error::ClipNotRepresentable => Status::ClipNotRepresentable,
+        cairo::Error::TempFileError => Status::TempFileError,
+        cairo::Error::InvalidStride => Status::InvalidStride,
+        cairo::Error::FontTypeMismatch => Status::FontTypeMismatch,
+        cairo::Error::UserFontImmutable => Status::UserFontImmutable,
+        cairo::Error::UserFontError => Status::UserFontError,
+        cairo::Error::NegativeCount => Status::NegativeCount,
+        cairo::Error::InvalidClusters => Status::InvalidClusters,
+        cairo::Error::InvalidSlant => Status::InvalidSlant,
+        cairo::Error::InvalidWeight => Status::InvalidWeight,
+        cairo::Error::InvalidSize => Status::InvalidSize,
+        cairo::Error::UserFontNotImplemented => Status::UserFontNotImplemented,
+        cairo::Error::DeviceTypeMismatch => Status::DeviceTypeMismatch,
+        cairo::Error::DeviceError => Status::DeviceError,
+        cairo::Error::InvalidMeshConstruction => Status::InvalidMeshConstruction,
+        cairo::Error::DeviceFinished => Status::DeviceFinished,
+        cairo::Error::JBig2GlobalMissing => Status::Jbig2GlobalMissing,
+        cairo::Error::PngError => Status::PngError,
+        cairo::Error::FreetypeError => Status::FreetypeError,
+        cairo::Error::Win32GdiError => Status::Win32GdiError,
+        _ => Status::Unknown,
+    }
+}
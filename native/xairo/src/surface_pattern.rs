@@ -0,0 +1,89 @@
+use crate::error::Error;
+use crate::matrix::{self, Matrix};
+use crate::pattern::{Extend, Filter};
+use crate::resource::{Guardable, Guarded};
+use crate::xairo_image::ImageArc;
+use rustler::ResourceArc;
+
+pub struct XairoSurfacePattern {
+    pub pattern: cairo::SurfacePattern,
+    /// The image the pattern reads from, kept so it can be returned to
+    /// Elixir as the same resource.
+    pub surface: ImageArc,
+}
+
+// SAFETY: the pattern reads the surface of the image it was created from, and
+// is created in that image's lock group. It is shared with the contexts it is
+// set on as a source, and `set_source` joins each of those into the group.
+unsafe impl Guardable for XairoSurfacePattern {}
+
+pub type SurfacePatternArc = ResourceArc<Guarded<XairoSurfacePattern>>;
+
+#[rustler::nif]
+fn surface_pattern_create(surface: ImageArc) -> Result<SurfacePatternArc, Error> {
+    let pattern = {
+        let image = surface.lock()?;
+        let pattern = cairo::SurfacePattern::create(image.surface());
+        // Match the scale the image was drawn at, so that the pattern is
+        // drawn at the same size in userspace.
+        pattern.set_matrix(image.context.matrix());
+        pattern
+    };
+
+    let pattern = Guarded::sharing(
+        XairoSurfacePattern {
+            pattern,
+            surface: surface.clone(),
+        },
+        &surface,
+    );
+    Ok(ResourceArc::new(pattern))
+}
+
+#[rustler::nif]
+fn surface_pattern_surface(pattern: SurfacePatternArc) -> Result<ImageArc, Error> {
+    Ok(pattern.lock()?.surface.clone())
+}
+
+#[rustler::nif]
+fn surface_pattern_set_matrix(
+    pattern: SurfacePatternArc,
+    matrix: Matrix,
+) -> Result<SurfacePatternArc, Error> {
+    let matrix = matrix::pattern_matrix(matrix)?;
+    pattern.lock()?.pattern.set_matrix(matrix);
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn surface_pattern_matrix(pattern: SurfacePatternArc) -> Result<Matrix, Error> {
+    Ok(pattern.lock()?.pattern.matrix().into())
+}
+
+#[rustler::nif]
+fn surface_pattern_set_extend(
+    pattern: SurfacePatternArc,
+    extend: Extend,
+) -> Result<SurfacePatternArc, Error> {
+    pattern.lock()?.pattern.set_extend(extend.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn surface_pattern_extend(pattern: SurfacePatternArc) -> Result<Extend, Error> {
+    Ok(pattern.lock()?.pattern.extend().into())
+}
+
+#[rustler::nif]
+fn surface_pattern_set_filter(
+    pattern: SurfacePatternArc,
+    filter: Filter,
+) -> Result<SurfacePatternArc, Error> {
+    pattern.lock()?.pattern.set_filter(filter.into());
+    Ok(pattern)
+}
+
+#[rustler::nif]
+fn surface_pattern_filter(pattern: SurfacePatternArc) -> Result<Filter, Error> {
+    Ok(pattern.lock()?.pattern.filter().into())
+}
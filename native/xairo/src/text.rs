@@ -1,5 +1,8 @@
 use crate::error::Error;
-use crate::xairo_image::ImageArc;
+use crate::path::{self, PathArc};
+use crate::resource;
+use crate::shaping::{self, FontFaceArc, ShapedGlyph, ShapingOptions};
+use crate::xairo_image::{ImageArc, ImageResult};
 
 #[derive(Copy, Clone, Debug, NifUnitEnum)]
 pub enum FontFamily {
@@ -23,6 +26,13 @@ pub enum FontWeight {
     Bold,
 }
 
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum TextAlignment {
+    Start,
+    Center,
+    End,
+}
+
 #[derive(Copy, Clone, Debug, NifStruct)]
 #[module = "Xairo.Text.Font"]
 pub struct Font {
@@ -45,31 +55,115 @@ pub struct Extents {
 }
 
 #[rustler::nif]
-fn set_font_size(image: ImageArc, font_size: f64) -> ImageArc {
-    image.context.set_font_size(font_size);
-    image
+fn set_font_size(image: ImageArc, font_size: f64) -> ImageResult {
+    image.lock()?.context.set_font_size(font_size);
+    Ok(image)
 }
 
-#[rustler::nif]
-fn show_text(image: ImageArc, text: &str) -> ImageArc {
-    image.context.show_text(text).unwrap();
-    image
+#[rustler::nif(schedule = "DirtyCpu")]
+fn show_text(image: ImageArc, text: &str) -> ImageResult {
+    let result = image.lock()?.context.show_text(text);
+    match result {
+        Ok(_) => Ok(image),
+        Err(_) => Err(Error::PathRender("show_text")),
+    }
 }
 
 #[rustler::nif]
-fn text_path(image: ImageArc, text: &str) -> ImageArc {
-    image.context.text_path(text);
-    image
+fn text_path(image: ImageArc, text: &str) -> ImageResult {
+    image.lock()?.context.text_path(text);
+    Ok(image)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn text_path_along(
+    image: ImageArc,
+    path: PathArc,
+    font_face: FontFaceArc,
+    text: &str,
+    offset: f64,
+    alignment: TextAlignment,
+    options: ShapingOptions,
+) -> ImageResult {
+    let segments = path.lock()?.segments()?;
+    let path_length: f64 = segments.iter().map(path::segment_length).sum();
+
+    {
+        let (guard, font_face) = resource::lock_pair(&image, &font_face)?;
+        let context = &guard.context;
+
+        let glyphs = shaping::shape(&font_face, context, text, options)?;
+        let text_length: f64 = glyphs.iter().map(|glyph| glyph.x_advance).sum();
+
+        let mut distance = offset
+            + match alignment {
+                TextAlignment::Start => 0.0,
+                TextAlignment::Center => (path_length - text_length) / 2.0,
+                TextAlignment::End => path_length - text_length,
+            };
+
+        // each cluster (e.g. a ligature, or a base glyph and its combining
+        // marks) is kept together, centered on, and rotated to the tangent of,
+        // the point on the path halfway along its advance
+        for cluster in clusters(&glyphs) {
+            let advance: f64 = cluster.iter().map(|glyph| glyph.x_advance).sum();
+            if let Some(((x, y), angle)) =
+                path::point_at_length(&segments, distance + advance / 2.0)
+            {
+                let mut pen = -advance / 2.0;
+                let cluster: Vec<cairo::Glyph> = cluster
+                    .iter()
+                    .map(|glyph| {
+                        let positioned = cairo::Glyph {
+                            index: glyph.index as _,
+                            x: pen + glyph.x_offset,
+                            y: glyph.y_offset,
+                        };
+                        pen += glyph.x_advance;
+                        positioned
+                    })
+                    .collect();
+
+                if context.save().is_err() {
+                    return Err(Error::TextAlongPath);
+                }
+                context.translate(x, y);
+                context.rotate(angle);
+                context.glyph_path(&cluster);
+                if context.restore().is_err() {
+                    return Err(Error::TextAlongPath);
+                }
+            }
+            distance += advance;
+        }
+    }
+
+    Ok(image)
+}
+
+/// Splits a run of shaped glyphs into the runs of consecutive glyphs that
+/// share a cluster.
+fn clusters(glyphs: &[ShapedGlyph]) -> Vec<&[ShapedGlyph]> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    for end in 1..=glyphs.len() {
+        if end == glyphs.len() || glyphs[end].cluster != glyphs[start].cluster {
+            clusters.push(&glyphs[start..end]);
+            start = end;
+        }
+    }
+    clusters
 }
 
 #[rustler::nif]
 fn text_extents(image: ImageArc, text: &str) -> Result<Extents, Error> {
-    if let Ok(extents) = image.context.text_extents(text) {
+    let guard = image.lock()?;
+    if let Ok(extents) = guard.context.text_extents(text) {
         Ok(Extents {
             text: text.to_string(),
             // a bit of a hack, since this assumes a non-sheared font matrix
             // but it'll do for now
-            font_size: image.context.font_matrix().yy,
+            font_size: guard.context.font_matrix().yy,
             x_bearing: extents.x_bearing,
             y_bearing: extents.y_bearing,
             width: extents.width,
@@ -83,15 +177,18 @@ fn text_extents(image: ImageArc, text: &str) -> Result<Extents, Error> {
 }
 
 #[rustler::nif]
-fn set_font_face(image: ImageArc, font: Font) -> ImageArc {
+fn set_font_face(image: ImageArc, font: Font) -> ImageResult {
     let family = match_font_family(font.family);
     let slant = match_font_slant(font.slant);
     let weight = match_font_weight(font.weight);
 
-    let font_face = cairo::FontFace::toy_create(family, slant, weight).unwrap();
-
-    image.context.set_font_face(&font_face);
-    image
+    match cairo::FontFace::toy_create(family, slant, weight) {
+        Ok(font_face) => {
+            image.lock()?.context.set_font_face(&font_face);
+            Ok(image)
+        }
+        Err(_) => Err(Error::FontFaceCreate),
+    }
 }
 
 fn match_font_slant(slant: FontSlant) -> cairo::FontSlant {
@@ -1,34 +1,55 @@
-use crate::xairo_image::ImageArc;
+use crate::xairo_image::{ImageArc, ImageResult};
 // use crate::shapes::{Arc, Curve, Point, Rectangle};
 use crate::matrix::Matrix;
+use crate::status;
 
 #[rustler::nif]
-fn translate(image: ImageArc, dx: f64, dy: f64) -> ImageArc {
-    image.context.translate(dx, dy);
-    image
+fn translate(image: ImageArc, dx: f64, dy: f64) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.translate(dx, dy);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn rotate(image: ImageArc, rad: f64) -> ImageArc {
-    image.context.rotate(rad);
-    image
+fn rotate(image: ImageArc, rad: f64) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.rotate(rad);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn transform(image: ImageArc, matrix: Matrix) -> ImageArc {
+fn transform(image: ImageArc, matrix: Matrix) -> ImageResult {
     let matrix = cairo::Matrix::from(matrix);
-    image.context.transform(matrix);
-    image
+    {
+        let guard = image.lock()?;
+        guard.context.transform(matrix);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-fn identity_matrix(image: ImageArc) -> ImageArc {
-    image.context.identity_matrix();
-    image
+fn identity_matrix(image: ImageArc) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.identity_matrix();
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
 
 #[rustler::nif]
-pub fn scale(image: ImageArc, sx: f64, sy: f64) -> ImageArc {
-    image.context.scale(sx, sy);
-    image
+pub fn scale(image: ImageArc, sx: f64, sy: f64) -> ImageResult {
+    {
+        let guard = image.lock()?;
+        guard.context.scale(sx, sy);
+        status::check(&guard.context)?;
+    }
+    Ok(image)
 }
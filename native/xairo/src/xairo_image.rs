@@ -1,6 +1,14 @@
-use crate::{error::Error, shapes::Point};
-use cairo::{Context, Format, ImageSurface, PdfSurface, PsSurface, SvgSurface};
+use crate::resource::{self, Guardable, Guarded};
+use crate::{
+    error::Error,
+    shapes::{Point, Rectangle},
+};
+use cairo::{
+    Content, Context, Format, ImageSurface, PdfSurface, PsSurface, RecordingSurface, Surface,
+    SurfaceType, SvgSurface,
+};
 use rustler::ResourceArc;
+use std::convert::TryFrom;
 use std::fs::File;
 
 #[derive(Copy, Clone, Debug, NifUnitEnum)]
@@ -17,12 +25,83 @@ pub enum SvgUnit {
     User,
 }
 
+/// The pixel format of an image surface.
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum ImageFormat {
+    Argb32,
+    Rgb24,
+    A8,
+    A1,
+    Rgb16565,
+    Rgb30,
+    Invalid,
+}
+
+impl From<Format> for ImageFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::ARgb32 => ImageFormat::Argb32,
+            Format::Rgb24 => ImageFormat::Rgb24,
+            Format::A8 => ImageFormat::A8,
+            Format::A1 => ImageFormat::A1,
+            Format::Rgb16_565 => ImageFormat::Rgb16565,
+            Format::Rgb30 => ImageFormat::Rgb30,
+            _ => ImageFormat::Invalid,
+        }
+    }
+}
+
+impl From<ImageFormat> for Format {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Argb32 => Format::ARgb32,
+            ImageFormat::Rgb24 => Format::Rgb24,
+            ImageFormat::A8 => Format::A8,
+            ImageFormat::A1 => Format::A1,
+            ImageFormat::Rgb16565 => Format::Rgb16_565,
+            ImageFormat::Rgb30 => Format::Rgb30,
+            ImageFormat::Invalid => Format::Invalid,
+        }
+    }
+}
+
+/// The cairo backend an image renders to.
+#[derive(Copy, Clone, Debug, NifUnitEnum)]
+pub enum Backend {
+    Image,
+    Svg,
+    Pdf,
+    Ps,
+    Recording,
+    Subsurface,
+    Other,
+}
+
+/// Information about an image's surface. The pixel dimensions, stride, and
+/// format are only set for image surfaces.
+#[derive(Debug, NifStruct)]
+#[module = "Xairo.ImageInfo"]
+pub struct ImageInfo {
+    pub backend: Backend,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub stride: Option<i32>,
+    pub format: Option<ImageFormat>,
+    pub device_scale: (f64, f64),
+    pub device_offset: (f64, f64),
+    pub finished: bool,
+}
+
 #[derive(Debug)]
 pub enum XairoSurface {
     Image(ImageSurface),
     Svg(SvgSurface),
     Pdf(PdfSurface),
     Ps(PsSurface),
+    Recording(RecordingSurface),
+    /// Surfaces whose type depends on the surface they were created from,
+    /// such as sub-surfaces.
+    Other(Surface),
 }
 
 #[derive(Debug)]
@@ -31,9 +110,18 @@ pub struct XairoImage {
     pub surface: XairoSurface,
 }
 
+// SAFETY: the context and surface are only reached through this resource,
+// through sub-images and surface patterns created in its lock group, or
+// through the resources it is joined with: the patterns `set_source` sets on
+// its context, and the recordings and masks that `replay` and `mask_surface`
+// paint onto it, which vector surfaces keep references to. Font faces set on
+// the context are never modified, and cairo locks their FreeType faces
+// itself.
+unsafe impl Guardable for XairoImage {}
+
 impl XairoImage {
-    pub fn new_png(width: i32, height: i32) -> Result<Self, Error> {
-        match ImageSurface::create(Format::ARgb32, width, height) {
+    pub fn new_png(width: i32, height: i32, format: ImageFormat) -> Result<Self, Error> {
+        match ImageSurface::create(format.into(), width, height) {
             Ok(surface) => match Context::new(&surface) {
                 Ok(context) => Ok(Self {
                     context,
@@ -84,9 +172,104 @@ impl XairoImage {
         }
     }
 
+    /// Creates a surface that records drawing operations so that they can be
+    /// replayed onto other images. Without `extents` the recording is
+    /// unbounded.
+    pub fn new_recording(extents: Option<Rectangle>) -> Result<Self, Error> {
+        let extents = extents.map(|rect| cairo::Rectangle {
+            x: rect.corner.x,
+            y: rect.corner.y,
+            width: rect.width,
+            height: rect.height,
+        });
+        match RecordingSurface::create(Content::ColorAlpha, extents) {
+            Ok(surface) => match Context::new(&surface) {
+                Ok(context) => Ok(Self {
+                    context,
+                    surface: XairoSurface::Recording(surface),
+                }),
+                Err(_) => Err(Error::ContextCreate),
+            },
+            Err(_) => Err(Error::SurfaceCreate),
+        }
+    }
+
+    /// Wraps a surface created from another surface, with a new context.
+    fn from_surface(surface: XairoSurface) -> Result<Self, Error> {
+        let context = match &surface {
+            XairoSurface::Image(surface) => Context::new(surface),
+            XairoSurface::Recording(surface) => Context::new(surface),
+            XairoSurface::Other(surface) => Context::new(surface),
+            _ => return Err(Error::SurfaceCreate),
+        };
+
+        match context {
+            Ok(context) => Ok(Self { context, surface }),
+            Err(_) => Err(Error::ContextCreate),
+        }
+    }
+
+    /// Returns a surface drawing into the given rectangle of this surface.
+    fn sub_surface(&self, rect: Rectangle) -> Result<Self, Error> {
+        let bounds = cairo::Rectangle {
+            x: rect.corner.x,
+            y: rect.corner.y,
+            width: rect.width,
+            height: rect.height,
+        };
+        match self.surface().create_for_rectangle(bounds) {
+            Ok(surface) => Self::from_surface(XairoSurface::Other(surface)),
+            Err(_) => Err(Error::SurfaceCreate),
+        }
+    }
+
+    /// Returns a new surface of the given size, of the type best suited for
+    /// drawing onto this surface.
+    fn similar(&self, width: i32, height: i32) -> Result<Self, Error> {
+        let surface = match self
+            .surface()
+            .create_similar(Content::ColorAlpha, width, height)
+        {
+            Ok(surface) => surface,
+            Err(_) => return Err(Error::SurfaceCreate),
+        };
+
+        let surface = match ImageSurface::try_from(surface) {
+            Ok(surface) => XairoSurface::Image(surface),
+            Err(surface) => match RecordingSurface::try_from(surface) {
+                Ok(surface) => XairoSurface::Recording(surface),
+                Err(surface) => XairoSurface::Other(surface),
+            },
+        };
+        Self::from_surface(surface)
+    }
+
+    /// Returns a new image surface of the given size and format, optimized
+    /// for drawing onto this surface.
+    fn similar_image(&self, format: ImageFormat, width: i32, height: i32) -> Result<Self, Error> {
+        let surface = self
+            .surface()
+            .create_similar_image(format.into(), width, height)
+            .ok()
+            .and_then(|surface| ImageSurface::try_from(surface).ok());
+
+        match surface {
+            Some(surface) => Self::from_surface(XairoSurface::Image(surface)),
+            None => Err(Error::SurfaceCreate),
+        }
+    }
+
     pub fn save(&self, filename: String) -> Result<(), Error> {
+        // Recordings and sub-surfaces have no file output of their own, and
+        // are saved through the image they are replayed or drawn onto.
+        if let XairoSurface::Recording(_) | XairoSurface::Other(_) = self.surface {
+            return Err(Error::FileTypeMismatch);
+        }
+
         match File::create(&filename) {
             Ok(mut file) => match &self.surface {
+                // cairo converts formats that PNG cannot store directly
+                // (such as Rgb16_565) to the closest format it can.
                 XairoSurface::Image(surface) => match surface.write_to_png(&mut file) {
                     Ok(_) => Ok(()),
                     Err(_) => Err(Error::FileWrite(filename)),
@@ -103,11 +286,87 @@ impl XairoImage {
                     surface.finish();
                     Ok(())
                 }
+                XairoSurface::Recording(_) | XairoSurface::Other(_) => Err(Error::FileTypeMismatch),
             },
             Err(_) => Err(Error::FileCreate(filename)),
         }
     }
 
+    /// Vector surfaces are finished when saved, after which they can no
+    /// longer be drawn to.
+    fn finishes_on_save(&self) -> bool {
+        !matches!(
+            self.surface,
+            XairoSurface::Image(_) | XairoSurface::Recording(_) | XairoSurface::Other(_)
+        )
+    }
+
+    /// Returns the surface as a generic cairo `Surface`.
+    pub fn surface(&self) -> &Surface {
+        match &self.surface {
+            XairoSurface::Image(surface) => surface,
+            XairoSurface::Svg(surface) => surface,
+            XairoSurface::Pdf(surface) => surface,
+            XairoSurface::Ps(surface) => surface,
+            XairoSurface::Recording(surface) => surface,
+            XairoSurface::Other(surface) => surface,
+        }
+    }
+
+    fn backend(&self) -> Backend {
+        match &self.surface {
+            XairoSurface::Image(_) => Backend::Image,
+            XairoSurface::Svg(_) => Backend::Svg,
+            XairoSurface::Pdf(_) => Backend::Pdf,
+            XairoSurface::Ps(_) => Backend::Ps,
+            XairoSurface::Recording(_) => Backend::Recording,
+            XairoSurface::Other(surface) => match surface.type_() {
+                SurfaceType::Subsurface => Backend::Subsurface,
+                _ => Backend::Other,
+            },
+        }
+    }
+
+    fn info(&self, finished: bool) -> ImageInfo {
+        let surface = self.surface();
+        let image_surface = match &self.surface {
+            XairoSurface::Image(surface) => Some(surface),
+            _ => None,
+        };
+
+        ImageInfo {
+            backend: self.backend(),
+            width: image_surface.map(|surface| surface.width()),
+            height: image_surface.map(|surface| surface.height()),
+            stride: image_surface.map(|surface| surface.stride()),
+            format: image_surface.map(|surface| surface.format().into()),
+            device_scale: surface.device_scale(),
+            device_offset: surface.device_offset(),
+            finished,
+        }
+    }
+
+    pub fn format(&self) -> Result<ImageFormat, Error> {
+        match &self.surface {
+            XairoSurface::Image(surface) => Ok(surface.format().into()),
+            _ => Err(Error::FileTypeMismatch),
+        }
+    }
+
+    /// Sets the resolution at which vector surfaces rasterize the parts of
+    /// a drawing they cannot represent natively.
+    fn set_fallback_resolution(&self, x_ppi: f64, y_ppi: f64) -> Result<(), Error> {
+        match &self.surface {
+            XairoSurface::Image(_) | XairoSurface::Recording(_) | XairoSurface::Other(_) => {
+                Err(Error::FileTypeMismatch)
+            }
+            _ => {
+                self.surface().set_fallback_resolution(x_ppi, y_ppi);
+                Ok(())
+            }
+        }
+    }
+
     fn set_document_unit(&self, unit: SvgUnit) -> Result<(), Error> {
         match &self.surface {
             XairoSurface::Svg(surface) => {
@@ -119,16 +378,13 @@ impl XairoImage {
     }
 }
 
-unsafe impl Send for XairoImage {}
-unsafe impl Sync for XairoImage {}
-
-pub type ImageArc = ResourceArc<XairoImage>;
+pub type ImageArc = ResourceArc<Guarded<XairoImage>>;
 pub type ImageResult = Result<ImageArc, Error>;
 
 #[rustler::nif]
-pub fn new_png_image(width: i32, height: i32) -> ImageResult {
-    match XairoImage::new_png(width, height) {
-        Ok(image) => Ok(ResourceArc::new(image)),
+pub fn new_png_image(width: i32, height: i32, format: ImageFormat) -> ImageResult {
+    match XairoImage::new_png(width, height, format) {
+        Ok(image) => Ok(ResourceArc::new(Guarded::new(image))),
         Err(e) => Err(e),
     }
 }
@@ -136,7 +392,7 @@ pub fn new_png_image(width: i32, height: i32) -> ImageResult {
 #[rustler::nif]
 pub fn new_pdf_image(width: f64, height: f64, filename: String) -> ImageResult {
     match XairoImage::new_pdf(width, height, filename) {
-        Ok(image) => Ok(ResourceArc::new(image)),
+        Ok(image) => Ok(ResourceArc::new(Guarded::new(image))),
         Err(e) => Err(e),
     }
 }
@@ -144,7 +400,7 @@ pub fn new_pdf_image(width: f64, height: f64, filename: String) -> ImageResult {
 #[rustler::nif]
 pub fn new_ps_image(width: f64, height: f64, filename: String) -> ImageResult {
     match XairoImage::new_ps(width, height, filename) {
-        Ok(image) => Ok(ResourceArc::new(image)),
+        Ok(image) => Ok(ResourceArc::new(Guarded::new(image))),
         Err(e) => Err(e),
     }
 }
@@ -152,50 +408,130 @@ pub fn new_ps_image(width: f64, height: f64, filename: String) -> ImageResult {
 #[rustler::nif]
 pub fn new_svg_image(width: f64, height: f64, filename: String) -> ImageResult {
     match XairoImage::new_svg(width, height, filename) {
-        Ok(image) => Ok(ResourceArc::new(image)),
+        Ok(image) => Ok(ResourceArc::new(Guarded::new(image))),
         Err(e) => Err(e),
     }
 }
 
 #[rustler::nif]
+pub fn new_recording(extents: Option<Rectangle>) -> ImageResult {
+    match XairoImage::new_recording(extents) {
+        Ok(image) => Ok(ResourceArc::new(Guarded::new(image))),
+        Err(e) => Err(e),
+    }
+}
+
+#[rustler::nif]
+pub fn create_for_rectangle(image: ImageArc, rect: Rectangle) -> ImageResult {
+    let sub_surface = image.lock()?.sub_surface(rect)?;
+    // The sub-surface draws into this image's surface, so it shares its lock.
+    Ok(ResourceArc::new(Guarded::sharing(sub_surface, &image)))
+}
+
+#[rustler::nif]
+pub fn create_similar(image: ImageArc, width: i32, height: i32) -> ImageResult {
+    let similar = image.lock()?.similar(width, height)?;
+    Ok(ResourceArc::new(Guarded::new(similar)))
+}
+
+#[rustler::nif]
+pub fn create_similar_image(
+    image: ImageArc,
+    format: ImageFormat,
+    width: i32,
+    height: i32,
+) -> ImageResult {
+    let similar = image.lock()?.similar_image(format, width, height)?;
+    Ok(ResourceArc::new(Guarded::new(similar)))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn save_image(image: ImageArc, filename: String) -> ImageResult {
-    match image.save(filename) {
-        Ok(_) => Ok(image),
+    let mut guard = image.lock()?;
+    match guard.save(filename) {
+        Ok(_) => {
+            if guard.finishes_on_save() {
+                guard.finish();
+            }
+            drop(guard);
+            Ok(image)
+        }
         Err(e) => Err(e),
     }
 }
 
 #[rustler::nif]
-pub fn set_document_unit(image: ImageArc, unit: SvgUnit) -> ImageResult {
-    match image.set_document_unit(unit) {
+pub fn image_info(image: ImageArc) -> Result<ImageInfo, Error> {
+    let (guard, finished) = image.lock_any()?;
+    Ok(guard.info(finished))
+}
+
+#[rustler::nif]
+pub fn image_format(image: ImageArc) -> Result<ImageFormat, Error> {
+    image.lock()?.format()
+}
+
+#[rustler::nif]
+pub fn set_device_scale(image: ImageArc, sx: f64, sy: f64) -> ImageResult {
+    image.lock()?.surface().set_device_scale(sx, sy);
+    Ok(image)
+}
+
+#[rustler::nif]
+pub fn device_scale(image: ImageArc) -> Result<(f64, f64), Error> {
+    Ok(image.lock()?.surface().device_scale())
+}
+
+#[rustler::nif]
+pub fn set_device_offset(image: ImageArc, x: f64, y: f64) -> ImageResult {
+    image.lock()?.surface().set_device_offset(x, y);
+    Ok(image)
+}
+
+#[rustler::nif]
+pub fn device_offset(image: ImageArc) -> Result<(f64, f64), Error> {
+    Ok(image.lock()?.surface().device_offset())
+}
+
+#[rustler::nif]
+pub fn set_fallback_resolution(image: ImageArc, x_ppi: f64, y_ppi: f64) -> ImageResult {
+    let result = image.lock()?.set_fallback_resolution(x_ppi, y_ppi);
+    match result {
         Ok(_) => Ok(image),
         Err(e) => Err(e),
     }
 }
 
 #[rustler::nif]
-pub fn mask_surface(image: ImageArc, mask: ImageArc, point: Point) -> ImageResult {
-    if let XairoSurface::Image(mask_surface) = &mask.surface {
-        match image.context.mask_surface(&mask_surface, point.x, point.y) {
-            Ok(_) => Ok(image),
-            Err(_) => Err(Error::MaskError),
-        }
-    } else {
-        Err(Error::MaskError)
-    }
+pub fn fallback_resolution(image: ImageArc) -> Result<(f64, f64), Error> {
+    Ok(image.lock()?.surface().fallback_resolution())
 }
 
 #[rustler::nif]
-fn set_surface_pattern_source(image: ImageArc, surface: ImageArc) -> ImageResult {
-    if let XairoSurface::Image(source_surface) = &surface.surface {
-        let surface_pattern = cairo::SurfacePattern::create(&source_surface);
-        surface_pattern.set_matrix(surface.context.matrix());
-        match image.context.set_source(&surface_pattern) {
-            Ok(_) => Ok(image),
-            Err(_) => Err(Error::SurfaceSource),
-        }
-    } else {
-        Err(Error::SurfaceSource)
+pub fn set_document_unit(image: ImageArc, unit: SvgUnit) -> ImageResult {
+    let result = image.lock()?.set_document_unit(unit);
+    match result {
+        Ok(_) => Ok(image),
+        Err(e) => Err(e),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn mask_surface(image: ImageArc, mask: ImageArc, point: Point) -> ImageResult {
+    // Vector surfaces keep a reference to the surfaces used as masks, so from
+    // now on saving the image also reads the mask.
+    resource::join(&image, &mask)?;
+    let result = {
+        let (target, mask) = resource::lock_pair(&image, &mask)?;
+        target
+            .context
+            .mask_surface(mask.surface(), point.x, point.y)
+            .map_err(|_| Error::MaskError)
+    };
+
+    match result {
+        Ok(_) => Ok(image),
+        Err(e) => Err(e),
     }
 }
 
@@ -22,4 +22,13 @@ defmodule Xairo.Image.SvgTest do
     |> Xairo.stroke()
     |> assert_image()
   end
+
+  test "an image cannot be drawn to after it has been saved" do
+    image =
+      Xairo.new_image("test.svg", 100, 100)
+      |> Xairo.save_image()
+
+    assert Xairo.move_to(image, {10, 10}) == {:error, {:resource_finished, nil}}
+    assert %Xairo.ImageInfo{backend: :svg, width: nil, finished: true} = Xairo.image_info(image)
+  end
 end
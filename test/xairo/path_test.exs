@@ -2,6 +2,11 @@ defmodule Xairo.PathTest do
   use ExUnit.Case, async: true
   import Xairo.Helpers.ImageHelpers
 
+  alias Xairo.Text
+  alias Xairo.Text.FontFace
+
+  @font_path "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf"
+
   test "copy_path" do
     image =
       Xairo.new_image("copy_path.png", 200, 200)
@@ -24,6 +29,90 @@ defmodule Xairo.PathTest do
     |> assert_image()
   end
 
+  test "polygon adds a closed path through packed points" do
+    points =
+      for {x, y} <- [{20, 20}, {150, 80}, {100, 180}], into: <<>> do
+        <<x::little-float-64, y::little-float-64>>
+      end
+
+    image =
+      Xairo.new_image("copy_path.png", 200, 200)
+      |> Xairo.set_color(1, 1, 1)
+      |> Xairo.paint()
+      |> Xairo.set_color(0, 0, 0)
+      |> Xairo.set_line_width(3)
+      |> Xairo.polygon(points)
+
+    path = Xairo.copy_path(image)
+
+    image
+    |> Xairo.fill()
+    |> Xairo.set_color(0, 0.5, 1)
+    |> Xairo.append_path(path)
+    |> Xairo.stroke()
+    |> assert_image()
+  end
+
+  test "polyline points can be exported from a path in the same packed format" do
+    image =
+      Xairo.new_image("test.png", 100, 100)
+      |> Xairo.polyline([{10, 10}, {50, 20}, {90, 90}])
+
+    binaries = image |> Xairo.copy_path() |> Xairo.Path.to_binary()
+
+    assert binaries == [
+             <<10.0::little-float-64, 10.0::little-float-64, 50.0::little-float-64,
+               20.0::little-float-64, 90.0::little-float-64, 90.0::little-float-64>>
+           ]
+  end
+
+  test "path points are exported with one binary per subpath" do
+    image =
+      Xairo.new_image("test.png", 100, 100)
+      |> Xairo.polygon([{10, 10}, {50, 10}, {50, 50}])
+      |> Xairo.polyline([{60, 60}, {90, 90}])
+
+    subpaths =
+      for binary <- image |> Xairo.copy_path() |> Xairo.Path.to_binary() do
+        for <<x::little-float-64, y::little-float-64 <- binary>>, do: {x, y}
+      end
+
+    assert subpaths == [
+             [{10.0, 10.0}, {50.0, 10.0}, {50.0, 50.0}, {10.0, 10.0}],
+             [{60.0, 60.0}, {90.0, 90.0}]
+           ]
+  end
+
+  test "a closed path exports its starting point once, with no empty subpath after it" do
+    image =
+      Xairo.new_image("test.png", 100, 100)
+      |> Xairo.polygon([{10, 10}, {50, 10}, {50, 50}])
+
+    assert [binary] = image |> Xairo.copy_path() |> Xairo.Path.to_binary()
+    assert byte_size(binary) == 4 * 16
+  end
+
+  test "path curves are flattened when exported" do
+    image =
+      Xairo.new_image("test.png", 100, 100)
+      |> Xairo.move_to({10, 10})
+      |> Xairo.curve_to({40, 90}, {60, 90}, {90, 10})
+
+    [binary] = image |> Xairo.copy_path() |> Xairo.Path.to_binary()
+    points = for <<x::little-float-64, y::little-float-64 <- binary>>, do: {x, y}
+
+    assert length(points) > 2
+    assert hd(points) == {10.0, 10.0}
+    assert List.last(points) == {90.0, 10.0}
+  end
+
+  test "polyline returns an error for a binary that is not a list of x/y pairs" do
+    image = Xairo.new_image("test.png", 100, 100)
+
+    assert Xairo.polyline(image, <<1.0::little-float-64, 2, 3>>) ==
+             {:error, {:point_data, 10}}
+  end
+
   test "copy_path_flat" do
     image = image_for_flat_tests()
 
@@ -103,6 +192,90 @@ defmodule Xairo.PathTest do
     |> assert_image()
   end
 
+  @tag macos: false
+  test "text_path_along adds glyph outlines that follow the given path" do
+    image =
+      Xairo.new_image("text_path_along.png", 100, 100)
+      |> Xairo.set_font_size(10)
+      |> Xairo.move_to({10, 50})
+      |> Xairo.line_to({90, 50})
+
+    path = Xairo.copy_path(image)
+
+    image = Xairo.new_path(image)
+    {_, %Xairo.Point{y: y_before}} = Xairo.Extents.new(image) |> Xairo.Extents.get(:path)
+    assert y_before == 0
+
+    image = Xairo.text_path_along(image, path, FontFace.load(@font_path), "Hi", align: :center)
+
+    {%Xairo.Point{x: x1}, %Xairo.Point{x: x2, y: y2}} =
+      Xairo.Extents.new(image) |> Xairo.Extents.get(:path)
+
+    assert x1 > 40 and x2 < 60
+    assert y2 <= 50.5
+  end
+
+  @tag macos: false
+  test "text_path_along lays out shaped text as it is on a straight line" do
+    face = FontFace.load(@font_path)
+
+    for {text, opts} <- [
+          {"AVAfi", []},
+          {"שלום", direction: :rtl, script: "Hebr"},
+          {"e\u0301e\u0301", []}
+        ] do
+      image =
+        Xairo.new_image("text_path_along.png", 200, 100)
+        |> Xairo.set_font_size(20)
+        |> Xairo.move_to({10, 50})
+        |> Xairo.line_to({190, 50})
+
+      path = Xairo.copy_path(image)
+
+      along =
+        image
+        |> Xairo.new_path()
+        |> Xairo.text_path_along(path, face, text, opts)
+        |> Xairo.Extents.new()
+        |> Xairo.Extents.get(:path)
+
+      image = image |> Xairo.new_path() |> Xairo.move_to({10, 50})
+
+      straight =
+        image
+        |> Xairo.glyph_path(Text.shape(image, face, text, opts))
+        |> Xairo.Extents.new()
+        |> Xairo.Extents.get(:path)
+
+      assert_same_extents(along, straight)
+    end
+  end
+
+  @tag macos: false
+  test "text_path_along skips characters that fall past the end of the path" do
+    image =
+      Xairo.new_image("text_path_along.png", 100, 100)
+      |> Xairo.move_to({10, 50})
+      |> Xairo.line_to({12, 50})
+
+    path = Xairo.copy_path(image)
+
+    image =
+      image
+      |> Xairo.new_path()
+      |> Xairo.text_path_along(path, FontFace.load(@font_path), "Hello", offset: 50)
+
+    assert Xairo.Extents.new(image) |> Xairo.Extents.get(:path) ==
+             {Xairo.Point.new(0, 0), Xairo.Point.new(0, 0)}
+  end
+
+  defp assert_same_extents({min1, max1}, {min2, max2}) do
+    for {p1, p2} <- [{min1, min2}, {max1, max2}] do
+      assert_in_delta p1.x, p2.x, 0.01
+      assert_in_delta p1.y, p2.y, 0.01
+    end
+  end
+
   defp image_for_flat_tests do
     Xairo.new_image("flat_test.png", 200, 200)
     |> Xairo.set_color(1, 1, 1)
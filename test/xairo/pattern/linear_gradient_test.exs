@@ -54,10 +54,40 @@ defmodule Xairo.Pattern.LinearGradientTest do
       |> LinearGradient.add_color_stop(0, RGBA.new(1, 0, 0))
       |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 1))
 
-    assert LinearGradient.color_stop(gradient, 0) == RGBA.new(1, 0, 0)
+    assert LinearGradient.color_stop(gradient, 0) == {0.0, RGBA.new(1, 0, 0)}
 
-    assert LinearGradient.color_stop(gradient, 2) ==
-             {:error, "No color stop found at index 2"}
+    assert LinearGradient.color_stop(gradient, 2) == {:error, {:color_stop, 2}}
+  end
+
+  describe "color_stops/1" do
+    test "returns every color stop with its offset" do
+      gradient =
+        LinearGradient.new({0, 0}, {0, 100})
+        |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 1))
+        |> LinearGradient.add_color_stop(0.25, RGBA.new(1, 0, 0))
+
+      assert LinearGradient.color_stops(gradient) == [
+               {0.25, RGBA.new(1, 0, 0)},
+               {1.0, RGBA.new(0, 0, 1)}
+             ]
+
+      assert LinearGradient.color_stops(LinearGradient.new({0, 0}, {0, 100})) == []
+    end
+  end
+
+  describe "set_color_stops/2" do
+    test "returns a copy of the gradient with the new color stops" do
+      gradient =
+        LinearGradient.new({0, 0}, {0, 100}, [{0, RGBA.new(1, 0, 0)}])
+        |> Xairo.Pattern.set_extend(:repeat)
+
+      rebuilt = LinearGradient.set_color_stops(gradient, [{0.5, RGBA.new(0, 1, 0)}])
+
+      assert LinearGradient.color_stops(rebuilt) == [{0.5, RGBA.new(0, 1, 0)}]
+      assert LinearGradient.linear_points(rebuilt) == {Point.new(0, 0), Point.new(0, 100)}
+      assert Xairo.Pattern.extend(rebuilt) == :repeat
+      assert LinearGradient.color_stops(gradient) == [{0.0, RGBA.new(1, 0, 0)}]
+    end
   end
 
   describe "linear_points" do
@@ -3,9 +3,10 @@ defmodule Xairo.Pattern.MeshTest do
 
   import Xairo.Helpers.ImageHelpers
 
-  alias Xairo.{Pattern.Mesh, Point, RGBA}
+  alias Xairo.{Curve, Pattern.Mesh, Pattern.Mesh.Patch, Point, RGBA}
 
   doctest Mesh
+  doctest Patch
 
   test "drawing with meshes" do
     mesh1 =
@@ -146,8 +147,7 @@ defmodule Xairo.Pattern.MeshTest do
       assert Mesh.control_point(mesh, 1, 1) == Point.new(0, 100)
       assert Mesh.control_point(mesh, 1, 2) == Point.new(150, -50)
 
-      assert Mesh.control_point(mesh, 2, 0) ==
-               {:error, "No control point set for corner 0 of patch 2"}
+      assert Mesh.control_point(mesh, 2, 0) == {:error, {:control_point, {0, 2}}}
     end
   end
 
@@ -169,7 +169,7 @@ defmodule Xairo.Pattern.MeshTest do
       assert Mesh.corner_color(mesh, 0, 0) == RGBA.new(1, 0, 0, 1)
       assert Mesh.corner_color(mesh, 0, 3) == RGBA.new(0, 0, 0, 0)
 
-      assert Mesh.corner_color(mesh, 1, 0) == {:error, "No color set for corner 0 of patch 1"}
+      assert Mesh.corner_color(mesh, 1, 0) == {:error, {:corner_color, {0, 1}}}
     end
   end
 
@@ -227,7 +227,167 @@ defmodule Xairo.Pattern.MeshTest do
         |> Mesh.set_corner_color(2, RGBA.new(0, 0, 1, 0.75))
         |> Mesh.end_patch()
 
-      assert Mesh.path(mesh, 1) == {:error, "No patch found at index 1"}
+      assert Mesh.path(mesh, 1) == {:error, {:mesh_path, 1}}
+    end
+  end
+
+  describe "building patches" do
+    test "returns an error for an invalid corner" do
+      mesh = Mesh.new() |> Mesh.begin_patch() |> Mesh.move_to({0, 0})
+
+      assert Mesh.set_corner_color(mesh, 4, RGBA.new(1, 0, 0)) == {:error, {:mesh_corner, 4}}
+      assert Mesh.set_control_point(mesh, 5, {10, 10}) == {:error, {:mesh_corner, 5}}
+    end
+
+    test "returns an error when no patch is in progress" do
+      mesh = Mesh.new()
+
+      assert {:error, {:mesh_construction, _}} = Mesh.line_to(mesh, {10, 10})
+      assert {:error, {:mesh_construction, _}} = Mesh.set_corner_color(mesh, 0, RGBA.new(1, 0, 0))
+      assert {:error, {:mesh_construction, _}} = Mesh.end_patch(mesh)
+    end
+
+    test "returns an error when beginning a patch inside another" do
+      mesh = Mesh.new() |> Mesh.begin_patch()
+
+      assert {:error, {:mesh_construction, _}} = Mesh.begin_patch(mesh)
+    end
+
+    test "returns an error when ending a patch with no points" do
+      mesh = Mesh.new() |> Mesh.begin_patch()
+
+      assert {:error, {:mesh_construction, _}} = Mesh.end_patch(mesh)
+    end
+
+    test "returns an error when adding a fifth side" do
+      mesh =
+        Mesh.new()
+        |> Mesh.begin_patch()
+        |> Mesh.move_to({0, 0})
+        |> Mesh.line_to({100, 0})
+        |> Mesh.line_to({100, 100})
+        |> Mesh.line_to({0, 100})
+        |> Mesh.line_to({0, 0})
+
+      assert {:error, {:mesh_construction, _}} = Mesh.line_to(mesh, {50, 50})
+      assert {:error, {:mesh_construction, _}} = Mesh.move_to(mesh, {50, 50})
+
+      assert Mesh.end_patch(mesh) == mesh
+      assert Mesh.patch_count(mesh) == 1
+    end
+  end
+
+  describe "from_patches/1" do
+    test "builds the same mesh as building each patch in turn" do
+      colors = [RGBA.new(1, 0, 0), RGBA.new(0, 1, 0), RGBA.new(0, 0, 1), RGBA.new(1, 1, 0)]
+
+      mesh =
+        Mesh.from_patches([
+          Patch.new(
+            {0, 0},
+            [
+              Curve.new({30, -30}, {60, 30}, {100, 0}),
+              {100, 100},
+              {0, 100}
+            ],
+            colors,
+            control_points: [nil, {80, 20}]
+          )
+        ])
+
+      assert Mesh.patch_count(mesh) == 1
+      assert Mesh.control_point(mesh, 0, 1) == Point.new(80, 20)
+      assert Mesh.corner_color(mesh, 0, 2) == RGBA.new(0, 0, 1)
+
+      image =
+        Xairo.new_image("patches.png", 100, 100)
+        |> Xairo.set_source(mesh)
+        |> Xairo.paint()
+
+      built =
+        Mesh.new()
+        |> Mesh.begin_patch()
+        |> Mesh.move_to({0, 0})
+        |> Mesh.curve_to({30, -30}, {60, 30}, {100, 0})
+        |> Mesh.line_to({100, 100})
+        |> Mesh.line_to({0, 100})
+        |> Mesh.set_control_point(1, {80, 20})
+        |> Mesh.set_corner_color(0, RGBA.new(1, 0, 0))
+        |> Mesh.set_corner_color(1, RGBA.new(0, 1, 0))
+        |> Mesh.set_corner_color(2, RGBA.new(0, 0, 1))
+        |> Mesh.set_corner_color(3, RGBA.new(1, 1, 0))
+        |> Mesh.end_patch()
+
+      expected =
+        Xairo.new_image("patches.png", 100, 100)
+        |> Xairo.set_source(built)
+        |> Xairo.paint()
+
+      assert Xairo.image_data(image) == Xairo.image_data(expected)
+    end
+
+    test "returns an error identifying an invalid patch" do
+      colors = [RGBA.new(1, 0, 0), RGBA.new(0, 1, 0), RGBA.new(0, 0, 1), RGBA.new(1, 1, 0)]
+
+      patches = [
+        Patch.new({0, 0}, [{100, 0}, {100, 100}, {0, 100}], colors),
+        Patch.new({0, 0}, [{100, 0}, {100, 100}], Enum.take(colors, 3))
+      ]
+
+      assert {:error, {:mesh_patch, {1, {:mesh_construction, _}}}} = Mesh.from_patches(patches)
+    end
+  end
+
+  describe "patches/1" do
+    test "can be used to rebuild the mesh" do
+      mesh =
+        Mesh.new()
+        |> Mesh.begin_patch()
+        |> Mesh.move_to({0, 0})
+        |> Mesh.curve_to({30, -30}, {60, 30}, {100, 0})
+        |> Mesh.line_to({100, 100})
+        |> Mesh.line_to({0, 100})
+        |> Mesh.set_corner_color(0, RGBA.new(1, 0, 0))
+        |> Mesh.set_corner_color(1, RGBA.new(0, 1, 0))
+        |> Mesh.set_corner_color(2, RGBA.new(0, 0, 1))
+        |> Mesh.end_patch()
+
+      assert [%Patch{} = patch] = Mesh.patches(mesh)
+
+      assert patch.start == Point.new(0, 0)
+      assert length(patch.sides) == 4
+      assert hd(patch.sides) == Curve.new({30, -30}, {60, 30}, {100, 0})
+      assert patch.control_points == Enum.map(0..3, &Mesh.control_point(mesh, 0, &1))
+      assert Enum.at(patch.corner_colors, 3) == RGBA.new(0, 0, 0, 0)
+
+      assert mesh |> Mesh.patches() |> Mesh.from_patches() |> Mesh.patches() == [patch]
+    end
+  end
+
+  describe "conic_gradient/4" do
+    test "blends colors around the center" do
+      mesh =
+        Mesh.conic_gradient({50, 50}, 50, 0, [
+          {0.25, RGBA.new(1, 0, 0)},
+          {0.75, RGBA.new(0, 0, 1)}
+        ])
+
+      image =
+        Xairo.new_image("conic.png", 100, 100)
+        |> Xairo.set_source(mesh)
+        |> Xairo.paint()
+
+      assert %{red: 1.0, blue: 0.0} = Xairo.get_pixel(image, 50, 90)
+      assert %{red: 0.0, blue: 1.0} = Xairo.get_pixel(image, 50, 10)
+      assert %{alpha: 0.0} = Xairo.get_pixel(image, 2, 2)
+    end
+
+    test "splits the turn into patches of at most a quarter turn" do
+      mesh = Mesh.conic_gradient({0, 0}, 10, 0, [{0, RGBA.new(1, 0, 0)}, {1, RGBA.new(0, 0, 1)}])
+
+      assert Mesh.patch_count(mesh) == 4
+      assert Mesh.corner_color(mesh, 0, 0) == RGBA.new(1, 0, 0)
+      assert Mesh.corner_color(mesh, 3, 2) == RGBA.new(0, 0, 1)
     end
   end
 end
@@ -58,9 +58,34 @@ defmodule Xairo.Pattern.RadialGradientTest do
         |> RadialGradient.add_color_stop(0.0, RGBA.new(1, 1, 1))
         |> RadialGradient.add_color_stop(1.0, RGBA.new(0.6, 0.6, 0.6))
 
-      assert RadialGradient.color_stop(gradient, 0) == RGBA.new(1, 1, 1)
+      assert RadialGradient.color_stop(gradient, 0) == {0.0, RGBA.new(1, 1, 1)}
 
-      assert RadialGradient.color_stop(gradient, 5) == {:error, "No color stop found at index 5"}
+      assert RadialGradient.color_stop(gradient, 5) == {:error, {:color_stop, 5}}
+    end
+  end
+
+  describe "color_stops/1" do
+    test "returns every color stop with its offset" do
+      gradient =
+        RadialGradient.new({20, 20}, 10, {20, 20}, 30, [
+          {0, RGBA.new(1, 1, 1)},
+          {1, RGBA.new(0.6, 0.6, 0.6)}
+        ])
+
+      assert RadialGradient.color_stops(gradient) == [
+               {0.0, RGBA.new(1, 1, 1)},
+               {1.0, RGBA.new(0.6, 0.6, 0.6)}
+             ]
+    end
+  end
+
+  describe "set_color_stops/2" do
+    test "returns a copy of the gradient with the new color stops" do
+      gradient = RadialGradient.new({20, 20}, 10, {20, 20}, 30, [{0, RGBA.new(1, 1, 1)}])
+      rebuilt = RadialGradient.set_color_stops(gradient, [])
+
+      assert RadialGradient.color_stops(rebuilt) == []
+      assert RadialGradient.radial_circles(rebuilt) == RadialGradient.radial_circles(gradient)
     end
   end
 
@@ -0,0 +1,56 @@
+defmodule Xairo.Pattern.RasterTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Pattern, Pattern.Raster, RGBA}
+
+  doctest Raster
+
+  describe "new/3" do
+    test "draws the given pixels" do
+      pattern = Raster.new(2, 1, <<255, 0, 0, 255, 0, 0, 255, 255>>)
+
+      image =
+        Xairo.new_image("raster.png", 2, 1)
+        |> Xairo.set_source(pattern)
+        |> Xairo.paint()
+
+      assert Xairo.get_pixel(image, 0, 0) == RGBA.new(1, 0, 0)
+      assert Xairo.get_pixel(image, 1, 0) == RGBA.new(0, 0, 1)
+    end
+
+    test "returns an error if the data does not match the size" do
+      assert Raster.new(2, 2, <<255, 0, 0, 255>>) == {:error, {:pixel_data, {4, 16}}}
+    end
+  end
+
+  describe "from_function/3" do
+    test "calls the function for each pixel" do
+      pattern =
+        Raster.from_function(20, 20, fn x, y ->
+          if div(x, 10) == div(y, 10), do: RGBA.new(0, 0, 0), else: RGBA.new(1, 1, 1)
+        end)
+        |> Pattern.set_extend(:repeat)
+
+      image =
+        Xairo.new_image("checkerboard.png", 100, 100)
+        |> Xairo.set_source(pattern)
+        |> Xairo.paint()
+
+      assert Xairo.get_pixel(image, 5, 5) == RGBA.new(0, 0, 0)
+      assert Xairo.get_pixel(image, 15, 5) == RGBA.new(1, 1, 1)
+      assert Xairo.get_pixel(image, 45, 45) == Xairo.get_pixel(image, 5, 5)
+      assert Xairo.get_pixel(image, 55, 45) == Xairo.get_pixel(image, 15, 5)
+    end
+
+    test "keeps transparent pixels transparent" do
+      pattern = Raster.from_function(1, 1, fn _, _ -> RGBA.new(1, 0, 0, 0) end)
+
+      image =
+        Xairo.new_image("transparent.png", 1, 1)
+        |> Xairo.set_source(pattern)
+        |> Xairo.paint()
+
+      assert %{alpha: 0.0} = Xairo.get_pixel(image, 0, 0)
+    end
+  end
+end
@@ -2,7 +2,7 @@ defmodule Xairo.Pattern.SurfaceTest do
   use ExUnit.Case, async: true
   import Xairo.Helpers.ImageHelpers
 
-  alias Xairo.{Pattern.LinearGradient, Pattern.Surface, RGBA}
+  alias Xairo.{Matrix, Pattern, Pattern.LinearGradient, Pattern.Surface, RGBA}
 
   describe "new/1" do
     test "returns a pattern from a surface" do
@@ -30,4 +30,109 @@ defmodule Xairo.Pattern.SurfaceTest do
       |> assert_image()
     end
   end
+
+  describe "similar/4" do
+    test "returns a surface compatible with the image" do
+      image = Xairo.new_image("similar.png", 100, 100, scale: 2)
+      pattern = Surface.similar(image, 10, 10)
+
+      assert %Xairo.ImageInfo{backend: :image, width: 20} = Xairo.image_info(pattern)
+    end
+
+    test "returns an image surface in the given format for vector images" do
+      image = Xairo.new_image("similar.pdf", 100, 100)
+
+      pattern =
+        Surface.similar(image, 10, 10, format: :rgb24)
+        |> Xairo.set_color(RGBA.new(1, 0, 0))
+        |> Xairo.paint()
+
+      assert %Xairo.ImageInfo{backend: :image, format: :rgb24} = Xairo.image_info(pattern)
+      assert %Xairo.Image.Pdf{} = image |> Xairo.set_source(pattern) |> Xairo.paint()
+
+      File.rm("similar.pdf")
+    end
+  end
+
+  describe "surface pattern resources" do
+    setup do
+      tile =
+        Surface.new(10, 10)
+        |> Xairo.set_color(RGBA.new(0, 0, 1))
+        |> Xairo.rectangle({0, 0}, 5, 5)
+        |> Xairo.fill()
+
+      {:ok, tile: tile}
+    end
+
+    test "can be reused as a source for several images", %{tile: tile} do
+      for filename <- ["first.png", "second.png"] do
+        image =
+          Xairo.new_image(filename, 10, 10)
+          |> Xairo.set_source(tile)
+          |> Xairo.paint()
+
+        assert %{blue: 1.0, alpha: 1.0} = Xairo.get_pixel(image, 2, 2)
+        assert %{alpha: 0.0} = Xairo.get_pixel(image, 7, 7)
+      end
+    end
+
+    test "can be used as a mask", %{tile: tile} do
+      image =
+        Xairo.new_image("masked.png", 10, 10)
+        |> Xairo.set_color(RGBA.new(1, 0, 0))
+        |> Xairo.mask(tile)
+
+      assert %{red: 1.0, alpha: 1.0} = Xairo.get_pixel(image, 2, 2)
+      assert %{alpha: 0.0} = Xairo.get_pixel(image, 7, 7)
+    end
+
+    test "has its own matrix", %{tile: tile} do
+      assert Pattern.matrix(tile) == Matrix.identity()
+
+      tile = Pattern.set_matrix(tile, Matrix.new() |> Matrix.scale(0.5, 0.5))
+
+      image =
+        Xairo.new_image("scaled.png", 20, 20)
+        |> Xairo.set_source(tile)
+        |> Xairo.paint()
+
+      assert %{blue: 1.0} = Xairo.get_pixel(image, 8, 8)
+      assert %{alpha: 0.0} = Xairo.get_pixel(image, 12, 12)
+
+      assert Pattern.set_matrix(tile, Matrix.new() |> Matrix.scale(0, 0)) ==
+               {:error, {:uninvertible_matrix, nil}}
+    end
+
+    test "can be created from an existing image and read back" do
+      image = Xairo.new_image("source.png", 10, 10)
+      pattern = Surface.from_image(image)
+
+      assert Surface.surface(pattern).resource == image.resource
+    end
+
+    test "can be drawn to while it is painted from other processes", %{tile: tile} do
+      drawing =
+        Task.async(fn ->
+          for i <- 1..20 do
+            tile
+            |> Xairo.move_to({0, i / 2})
+            |> Xairo.line_to({10, i / 2})
+            |> Xairo.stroke()
+          end
+        end)
+
+      1..20
+      |> Enum.map(fn i ->
+        Task.async(fn ->
+          Xairo.new_image("painted_#{i}.png", 10, 10)
+          |> Xairo.set_source(tile)
+          |> Xairo.paint()
+        end)
+      end)
+      |> Enum.each(fn task -> assert %Xairo.Image.Png{} = Task.await(task) end)
+
+      assert Enum.all?(Task.await(drawing), &match?(%Surface{}, &1))
+    end
+  end
 end
@@ -0,0 +1,174 @@
+defmodule Xairo.PatternTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Matrix, Pattern, Point, Rectangle, RGBA}
+  alias Xairo.Pattern.{LinearGradient, Mesh, RadialGradient, Raster, Solid, Surface}
+
+  defp patterns do
+    [
+      LinearGradient.new({0, 0}, {10, 0}),
+      RadialGradient.new({5, 5}, 0, {5, 5}, 5),
+      Mesh.new(),
+      Solid.new(RGBA.new(1, 0, 0)),
+      Surface.new(10, 10),
+      Raster.new(1, 1, <<255, 0, 0, 255>>)
+    ]
+  end
+
+  test "every pattern type reports its type" do
+    assert Enum.map(patterns(), &Pattern.type/1) ==
+             [:linear_gradient, :radial_gradient, :mesh, :solid, :surface, :raster]
+  end
+
+  test "every pattern type can be set as a source and used as a mask" do
+    for pattern <- patterns() do
+      image = Xairo.new_image("pattern.png", 10, 10)
+
+      assert image |> Xairo.set_source(pattern) |> Xairo.paint() == image
+      assert image |> Xairo.set_color(RGBA.new(1, 0, 0)) |> Xairo.mask(pattern) == image
+    end
+  end
+
+  test "extend modes can be set on every pattern type" do
+    for pattern <- patterns(), extend <- [:none, :repeat, :reflect, :pad] do
+      assert pattern |> Pattern.set_extend(extend) |> Pattern.extend() == extend
+    end
+  end
+
+  test "filters can be set on every pattern type" do
+    for pattern <- patterns(), filter <- [:fast, :good, :best, :nearest, :bilinear, :gaussian] do
+      assert pattern |> Pattern.set_filter(filter) |> Pattern.filter() == filter
+    end
+  end
+
+  test "matrices can be set on every pattern type" do
+    matrix = Matrix.new() |> Matrix.scale(2, 3) |> Matrix.translate(1, 1)
+
+    for pattern <- patterns() do
+      assert Pattern.matrix(pattern) == Matrix.identity()
+      assert pattern |> Pattern.set_matrix(matrix) |> Pattern.matrix() == matrix
+    end
+  end
+
+  test "pattern matrices must be invertible" do
+    for pattern <- patterns() do
+      assert Pattern.set_matrix(pattern, Matrix.new() |> Matrix.scale(0, 1)) ==
+               {:error, {:uninvertible_matrix, nil}}
+    end
+  end
+
+  test "a gradient's matrix transforms it independently of the path" do
+    gradient =
+      LinearGradient.new({0, 0}, {10, 0})
+      |> LinearGradient.add_color_stop(0, RGBA.new(1, 0, 0))
+      |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 1))
+      |> Pattern.set_matrix(Matrix.new() |> Matrix.rotate(-:math.pi() / 2))
+
+    image =
+      Xairo.new_image("rotated_gradient.png", 10, 10)
+      |> Xairo.set_source(gradient)
+      |> Xairo.paint()
+
+    # The rotated matrix maps each point's y coordinate onto the gradient, so
+    # it runs from red at the top of the image to blue at the bottom.
+    assert Xairo.get_pixel(image, 0, 5) == Xairo.get_pixel(image, 9, 5)
+
+    %{red: top_red, blue: top_blue} = Xairo.get_pixel(image, 5, 0)
+    assert top_red > 0.9 and top_blue < 0.1
+
+    %{red: bottom_red, blue: bottom_blue} = Xairo.get_pixel(image, 5, 9)
+    assert bottom_red < 0.1 and bottom_blue > 0.9
+  end
+
+  test "gradients pad and surfaces do not extend by default" do
+    assert Pattern.extend(LinearGradient.new({0, 0}, {10, 0})) == :pad
+    assert Pattern.extend(Surface.new(10, 10)) == :none
+  end
+
+  test "a repeating gradient repeats its color stops" do
+    gradient =
+      LinearGradient.new({0, 0}, {10, 0})
+      |> LinearGradient.add_color_stop(0, RGBA.new(1, 0, 0))
+      |> LinearGradient.add_color_stop(1, RGBA.new(0, 0, 1))
+      |> Pattern.set_extend(:repeat)
+
+    image =
+      Xairo.new_image("extend.png", 100, 10)
+      |> Xairo.set_source(gradient)
+      |> Xairo.paint()
+
+    assert Xairo.get_pixel(image, 50, 5) == Xairo.get_pixel(image, 0, 5)
+  end
+
+  test "a repeating surface pattern tiles the image" do
+    tile =
+      Surface.new(10, 10)
+      |> Xairo.set_color(0, 0, 1)
+      |> Xairo.rectangle({0, 0}, 5, 5)
+      |> Xairo.fill()
+
+    image =
+      Xairo.new_image("tiles.png", 100, 100)
+      |> Xairo.set_source(Pattern.set_extend(tile, :repeat))
+      |> Xairo.paint()
+
+    assert %{blue: 1.0, alpha: 1.0} = Xairo.get_pixel(image, 52, 52)
+    assert %{alpha: 0.0} = Xairo.get_pixel(image, 57, 57)
+  end
+
+  describe "from_css/2" do
+    setup do
+      {:ok, rect: Rectangle.new({0, 0}, 100, 50)}
+    end
+
+    test "builds a linear gradient from a direction and color stops", %{rect: rect} do
+      gradient =
+        Pattern.from_css("linear-gradient(to right, #f00 0%, rgba(0, 0, 255, .5) 100%)", rect)
+
+      assert %LinearGradient{} = gradient
+      assert LinearGradient.linear_points(gradient) == {Point.new(0, 25), Point.new(100, 25)}
+
+      assert LinearGradient.color_stops(gradient) == [
+               {0.0, RGBA.new(1, 0, 0)},
+               {1.0, RGBA.new(0, 0, 1, 0.5)}
+             ]
+
+      assert Pattern.extend(gradient) == :pad
+    end
+
+    test "defaults to top to bottom and spaces out unpositioned stops", %{rect: rect} do
+      gradient = Pattern.from_css("linear-gradient(red, lime, blue)", rect)
+
+      assert LinearGradient.linear_points(gradient) == {Point.new(50, 0), Point.new(50, 50)}
+      assert [{0.0, _}, {0.5, _}, {1.0, _}] = LinearGradient.color_stops(gradient)
+    end
+
+    test "maps repeating gradients to a repeating extend", %{rect: rect} do
+      gradient =
+        Pattern.from_css("repeating-linear-gradient(to right, red 10px, blue 20px)", rect)
+
+      assert Pattern.extend(gradient) == :repeat
+      assert LinearGradient.linear_points(gradient) == {Point.new(10, 25), Point.new(20, 25)}
+    end
+
+    test "builds a radial gradient", %{rect: rect} do
+      gradient = Pattern.from_css("radial-gradient(circle 10px at 30% 40%, white, navy)", rect)
+
+      assert %RadialGradient{} = gradient
+      assert RadialGradient.radial_circles(gradient) ==
+               {{Point.new(30, 20), 0.0}, {Point.new(30, 20), 10.0}}
+    end
+
+    test "scales elliptical radial gradients with the pattern matrix", %{rect: rect} do
+      gradient = Pattern.from_css("radial-gradient(ellipse closest-side, white, navy)", rect)
+
+      assert Pattern.matrix(gradient) == Matrix.new(yy: 2, xt: -50, yt: -50)
+    end
+
+    test "returns an error for invalid CSS", %{rect: rect} do
+      for css <- ["conic-gradient(red, blue)", "linear-gradient(to nowhere, red)", "redish"] do
+        assert {:error, {:css_gradient, _}} = Pattern.from_css(css, rect)
+      end
+    end
+  end
+end
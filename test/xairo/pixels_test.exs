@@ -0,0 +1,102 @@
+defmodule Xairo.PixelsTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.RGBA
+
+  setup do
+    image =
+      Xairo.new_image("pixels.png", 10, 10, scale: 2.0)
+      |> Xairo.set_color(1, 0, 0, 0.5)
+      |> Xairo.rectangle({0, 0}, 5, 5)
+      |> Xairo.fill()
+
+    {:ok, image: image}
+  end
+
+  test "image_data returns the raw pixel data with its layout", %{image: image} do
+    %{data: data, width: width, height: height, stride: stride, format: format} =
+      Xairo.image_data(image)
+
+    assert {width, height, format} == {20, 20, :argb32}
+    assert byte_size(data) == stride * height
+  end
+
+  test "get_pixel returns un-premultiplied colors", %{image: image} do
+    assert %RGBA{red: red, green: 0.0, blue: 0.0, alpha: alpha} = Xairo.get_pixel(image, 5, 5)
+    assert_in_delta red, 1.0, 0.01
+    assert_in_delta alpha, 0.5, 0.01
+
+    assert Xairo.get_pixel(image, 15, 15) == %RGBA{red: 0.0, green: 0.0, blue: 0.0, alpha: 0.0}
+  end
+
+  test "get_pixel returns an error outside the surface", %{image: image} do
+    assert Xairo.get_pixel(image, 20, 0) == {:error, {:pixel_out_of_bounds, {20, 0}}}
+  end
+
+  test "set_image_data writes pixel data back to the surface", %{image: image} do
+    %{data: data} = Xairo.image_data(image)
+    blank = Xairo.new_image("blank.png", 10, 10, scale: 2.0)
+
+    blank = Xairo.set_image_data(blank, data)
+
+    assert Xairo.image_data(blank).data == data
+    assert Xairo.get_pixel(blank, 5, 5) == Xairo.get_pixel(image, 5, 5)
+  end
+
+  test "set_image_data returns an error for data of the wrong size", %{image: image} do
+    assert Xairo.set_image_data(image, <<0, 0, 0, 0>>) == {:error, {:pixel_data, {4, 1600}}}
+  end
+
+  test "pixel access returns an error for vector images" do
+    image = Xairo.new_image("pixels.svg", 10, 10)
+
+    assert Xairo.image_data(image) == {:error, {:file_type_mismatch, nil}}
+
+    File.rm("pixels.svg")
+  end
+
+  describe "image formats" do
+    test "images are created as argb32 by default", %{image: image} do
+      assert Xairo.image_format(image) == :argb32
+    end
+
+    test "rgb24 images are opaque" do
+      image =
+        Xairo.new_image("rgb24.png", 10, 10, format: :rgb24)
+        |> Xairo.set_color(0, 0, 1, 0.5)
+        |> Xairo.paint()
+
+      assert Xairo.image_format(image) == :rgb24
+      assert %RGBA{red: 0.0, green: 0.0, alpha: 1.0} = Xairo.get_pixel(image, 5, 5)
+    end
+
+    test "alpha-only formats store only the alpha value" do
+      for format <- [:a8, :a1] do
+        mask =
+          Xairo.Mask.new(40, 10, format: format)
+          |> Xairo.set_color(1, 0, 0)
+          |> Xairo.rectangle({0, 0}, 35, 10)
+          |> Xairo.fill()
+
+        assert Xairo.image_format(mask) == format
+        assert Xairo.get_pixel(mask, 34, 5) == RGBA.new(0, 0, 0, 1)
+        assert Xairo.get_pixel(mask, 36, 5) == RGBA.new(0, 0, 0, 0)
+      end
+    end
+
+    test "images in every format can be saved" do
+      for format <- [:argb32, :rgb24, :a8, :a1, :rgb16565, :rgb30] do
+        filename = "#{format}.png"
+
+        image =
+          Xairo.new_image(filename, 10, 10, format: format)
+          |> Xairo.set_color(1, 1, 1)
+          |> Xairo.paint()
+
+        assert %Xairo.Image.Png{} = Xairo.save_image(image)
+        assert File.exists?(filename)
+        File.rm(filename)
+      end
+    end
+  end
+end
@@ -0,0 +1,66 @@
+defmodule Xairo.RecordingTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.{Point, Recording, Rectangle}
+
+  setup do
+    recording =
+      Recording.new()
+      |> Xairo.set_color(1, 0, 0)
+      |> Xairo.rectangle({10, 20}, 30, 40)
+      |> Xairo.fill()
+
+    {:ok, recording: recording}
+  end
+
+  test "ink_extents returns the bounds of everything drawn", %{recording: recording} do
+    assert Recording.ink_extents(recording) == Rectangle.new({10, 20}, 30, 40)
+  end
+
+  test "a recording can be replayed onto an image", %{recording: recording} do
+    image =
+      Xairo.new_image("replay.png", 100, 100)
+      |> Xairo.replay(recording, {50, 0})
+
+    assert %{red: 1.0, alpha: 1.0} = Xairo.get_pixel(image, 65, 25)
+    assert %{alpha: 0.0} = Xairo.get_pixel(image, 15, 25)
+  end
+
+  test "a recording can be scaled when replayed", %{recording: recording} do
+    image =
+      Xairo.new_image("replay.png", 100, 100)
+      |> Xairo.replay(recording, {0, 0}, scale: {2, 1})
+
+    assert %{alpha: 1.0} = Xairo.get_pixel(image, 75, 25)
+    assert Xairo.get_matrix(image) == Xairo.Matrix.identity()
+  end
+
+  test "a recording can be replayed onto vector images", %{recording: recording} do
+    Xairo.new_image("replay.svg", 100, 100)
+    |> Xairo.set_color(0.5, 0, 1)
+    |> Xairo.paint()
+    |> Xairo.replay(recording)
+    |> Xairo.save_image()
+
+    assert File.read!("replay.svg") =~ "rgb(100%, 0%, 0%)"
+    File.rm("replay.svg")
+  end
+
+  test "bounded recordings only record within their extents" do
+    recording =
+      Recording.new(50, 50)
+      |> Xairo.set_color(0, 0, 1)
+      |> Xairo.paint()
+
+    assert Recording.ink_extents(recording) == Rectangle.new({0, 0}, 50, 50)
+    assert %Xairo.ImageInfo{backend: :recording} = Xairo.image_info(recording)
+  end
+
+  test "replay is not supported from images that are not recordings" do
+    image = Xairo.new_image("replay.png", 100, 100)
+    not_a_recording = %Recording{resource: Xairo.new_image("other.png", 10, 10).resource}
+
+    assert Xairo.replay(image, not_a_recording, Point.new(0, 0)) ==
+             {:error, {:file_type_mismatch, nil}}
+  end
+end
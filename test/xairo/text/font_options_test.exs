@@ -0,0 +1,28 @@
+defmodule Xairo.Text.FontOptionsTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.Text
+  alias Xairo.Text.FontOptions
+  doctest FontOptions
+
+  test "font options set on an image can be read back" do
+    options = FontOptions.new(antialias: :gray, hint_style: :full, hint_metrics: :on)
+
+    image =
+      Xairo.new_image("font_options.png", 100, 100)
+      |> Xairo.set_font_options(options)
+
+    assert Text.font_options(image) == options
+  end
+
+  test "an image's font options default to letting cairo decide" do
+    image = Xairo.new_image("font_options.png", 100, 100)
+
+    assert Text.font_options(image) == FontOptions.new()
+  end
+
+  test "creating font options with an invalid value returns an error" do
+    assert FontOptions.new(hint_metrics: :sometimes) ==
+             {:error, {:invalid_hint_metrics, :sometimes}}
+  end
+end
@@ -0,0 +1,102 @@
+defmodule Xairo.Text.ShapingTest do
+  use ExUnit.Case, async: true
+
+  alias Xairo.Text
+  alias Xairo.Text.{FontFace, Glyph, ShapingOptions}
+
+  doctest ShapingOptions
+
+  @font_path "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf"
+
+  test "loading a font face from a file that does not exist returns an error" do
+    assert FontFace.load("nope/nope.ttf") == {:error, {:font_load, "nope/nope.ttf"}}
+  end
+
+  @tag macos: false
+  test "shape returns one positioned glyph per character for simple text" do
+    face = FontFace.load(@font_path)
+
+    glyphs =
+      Xairo.new_image("shaping.png", 100, 100)
+      |> Xairo.set_font_size(20)
+      |> Xairo.move_to({10, 50})
+      |> Text.shape(face, "hello")
+
+    assert length(glyphs) == 5
+    assert [%Glyph{x: 10.0, y: 50.0} | _] = glyphs
+
+    xs = Enum.map(glyphs, & &1.x)
+    assert xs == Enum.sort(xs)
+  end
+
+  @tag macos: false
+  test "shape lays out right-to-left text in visual order" do
+    face = FontFace.load(@font_path)
+    image = Xairo.new_image("shaping.png", 100, 100) |> Xairo.set_font_size(20)
+
+    [shin, lamed, vav, final_mem] =
+      for letter <- ~w(ש ל ו ם), do: glyph_index(image, face, letter)
+
+    glyphs = Text.shape(image, face, "שלום", direction: :rtl, script: "Hebr")
+
+    assert Enum.map(glyphs, & &1.index) == [final_mem, vav, lamed, shin]
+
+    xs = Enum.map(glyphs, & &1.x)
+    assert xs == Enum.sort(xs)
+  end
+
+  @tag macos: false
+  test "shape replaces ligatures with a single glyph" do
+    face = FontFace.load(@font_path)
+    image = Xairo.new_image("shaping.png", 100, 100) |> Xairo.set_font_size(20)
+
+    assert [%Glyph{index: index}] = Text.shape(image, face, "fi")
+    refute index in [glyph_index(image, face, "f"), glyph_index(image, face, "i")]
+  end
+
+  @tag macos: false
+  test "shape picks contextual forms for joining scripts" do
+    face = FontFace.load(@font_path)
+    image = Xairo.new_image("shaping.png", 100, 100) |> Xairo.set_font_size(20)
+
+    isolated = glyph_index(image, face, "ب")
+
+    glyphs = Text.shape(image, face, "ببب", direction: :rtl, script: "Arab", language: "ar")
+
+    assert length(glyphs) == 3
+    assert [final, medial, initial] = Enum.map(glyphs, & &1.index)
+    assert Enum.uniq([isolated, initial, medial, final]) == [isolated, initial, medial, final]
+  end
+
+  @tag macos: false
+  test "shape returns an error for an invalid direction" do
+    face = FontFace.load(@font_path)
+
+    assert Xairo.new_image("shaping.png", 100, 100)
+           |> Text.shape(face, "hello", direction: :sideways) ==
+             {:error, {:invalid_direction, :sideways}}
+  end
+
+  @tag macos: false
+  test "shape returns an error for an unknown script" do
+    face = FontFace.load(@font_path)
+
+    assert Xairo.new_image("shaping.png", 100, 100)
+           |> Text.shape(face, "hello", script: "!!") == {:error, {:invalid_script, "!!"}}
+  end
+
+  @tag macos: false
+  test "show_shaped_text renders glyphs without error" do
+    face = FontFace.load(@font_path)
+
+    assert %Xairo.Image.Png{} =
+             Xairo.new_image("shaping.png", 100, 100)
+             |> Xairo.move_to({10, 50})
+             |> Xairo.show_shaped_text(face, "hello", direction: :ltr)
+  end
+
+  defp glyph_index(image, face, letter) do
+    [%Glyph{index: index}] = Text.shape(image, face, letter)
+    index
+  end
+end
@@ -14,11 +14,126 @@ defmodule XairoTest do
   end
 
   test "error handling for bad filename" do
-    assert {:error, "Error creating file at nope/nope/nope.png"} ==
+    assert {:error, {:file_create, "nope/nope/nope.png"}} ==
              Xairo.new_image("nope/nope/nope.png", 100, 100)
              |> Xairo.save_image()
   end
 
+  describe "status/1" do
+    test "returns :success for a valid image", %{image: image} do
+      assert Xairo.status(image) == :success
+    end
+
+    test "returns an error from drawing once the context is invalid", %{image: image} do
+      assert Xairo.scale(image, 0, 0) == {:error, {:invalid_status, :invalid_matrix}}
+      assert Xairo.status(image) == :invalid_matrix
+
+      assert Xairo.line_to(image, {10, 10}) == {:error, {:invalid_status, :invalid_matrix}}
+    end
+  end
+
+  describe "image_info/1" do
+    test "reads the dimensions from the surface", %{image: image} do
+      assert %Xairo.ImageInfo{
+               backend: :image,
+               width: 200,
+               height: 200,
+               stride: 800,
+               format: :argb32,
+               finished: false
+             } = Xairo.image_info(image)
+    end
+
+    test "reports the size of a scaled mask" do
+      mask = Xairo.Mask.new(15, 15, scale: 1.5)
+
+      assert %Xairo.ImageInfo{width: 23, height: 23} = Xairo.image_info(mask)
+    end
+  end
+
+  describe "device scale and offset" do
+    test "scales drawing without changing the matrix" do
+      image =
+        Xairo.new_image("device.png", 100, 100)
+        |> Xairo.set_device_scale(2, 2)
+        |> Xairo.set_device_offset(10, 0)
+        |> Xairo.rectangle({0, 0}, 10, 10)
+        |> Xairo.fill()
+
+      assert Xairo.device_scale(image) == {2.0, 2.0}
+      assert Xairo.device_offset(image) == {10.0, 0.0}
+      assert Xairo.get_matrix(image) == Matrix.identity()
+
+      assert %{alpha: 1.0} = Xairo.get_pixel(image, 29, 19)
+      assert %{alpha: 0.0} = Xairo.get_pixel(image, 31, 19)
+    end
+
+    test "fallback resolution can only be set on vector images", %{image: image} do
+      assert Xairo.set_fallback_resolution(image, 600, 600) ==
+               {:error, {:file_type_mismatch, nil}}
+
+      pdf =
+        Xairo.new_image("fallback.pdf", 100, 100)
+        |> Xairo.set_fallback_resolution(600, 150)
+
+      assert Xairo.fallback_resolution(pdf) == {600.0, 150.0}
+
+      File.rm("fallback.pdf")
+    end
+  end
+
+  describe "sub_image/2" do
+    test "draws into a region of the image", %{image: image} do
+      sub_image = Xairo.sub_image(image, Rectangle.new({50, 0}, 50, 50))
+
+      assert %Xairo.Image.Png{width: 50, height: 50, scale: 2.0} = sub_image
+      assert %Xairo.ImageInfo{backend: :subsurface} = Xairo.image_info(sub_image)
+
+      sub_image
+      |> Xairo.set_color(1, 0, 0)
+      |> Xairo.paint()
+
+      assert %{red: 1.0, green: 0.0} = Xairo.get_pixel(image, 150, 50)
+      assert %{red: 1.0, green: 1.0} = Xairo.get_pixel(image, 90, 50)
+      assert %{red: 1.0, green: 1.0} = Xairo.get_pixel(image, 150, 110)
+    end
+
+    test "cannot be saved on its own", %{image: image} do
+      sub_image = Xairo.sub_image(image, Rectangle.new({0, 0}, 50, 50))
+
+      assert Xairo.save_image(sub_image) == {:error, {:file_type_mismatch, nil}}
+    end
+
+    test "can be drawn from processes concurrently with its image", %{image: image} do
+      sub_image = Xairo.sub_image(image, Rectangle.new({0, 0}, 50, 50))
+
+      [image, sub_image]
+      |> Enum.map(fn target ->
+        Task.async(fn ->
+          target
+          |> Xairo.move_to({0, 0})
+          |> Xairo.line_to({50, 50})
+          |> Xairo.stroke()
+        end)
+      end)
+      |> Enum.each(fn task -> assert %Xairo.Image.Png{} = Task.await(task) end)
+    end
+  end
+
+  test "an image can be drawn to from multiple processes", %{image: image} do
+    1..20
+    |> Enum.map(fn i ->
+      Task.async(fn ->
+        image
+        |> Xairo.move_to({i, i})
+        |> Xairo.line_to({100 - i, 100 - i})
+        |> Xairo.stroke()
+      end)
+    end)
+    |> Enum.map(&Task.await/1)
+    |> Enum.each(fn result -> assert %Xairo.Image.Png{} = result end)
+  end
+
   test "proper return for valid filename" do
     %Xairo.Image.Png{} =
       Xairo.new_image("yep.png", 100, 100)
@@ -48,6 +163,26 @@ defmodule XairoTest do
     |> assert_image()
   end
 
+  describe "draw_commands/2" do
+    test "draws the same image as the equivalent individual calls" do
+      Xairo.new_image("diagonal.png", 100, 100)
+      |> Xairo.draw_commands([{:move_to, {10, 10}}, {:line_to, {90, 90}}, :stroke])
+      |> assert_image()
+    end
+
+    test "returns the index of the first failing command", %{image: image} do
+      commands = [{:move_to, {10, 10}}, {:scale, 0, 0}, {:line_to, {20, 20}}]
+
+      assert Xairo.draw_commands(image, commands) ==
+               {:error, {:draw_command, {1, {:invalid_status, :invalid_matrix}}}}
+    end
+
+    test "returns an error for an unknown command", %{image: image} do
+      assert Xairo.draw_commands(image, [:stroke, {:jump_to, {10, 10}}]) ==
+               {:error, {:draw_command, {1, {:invalid_draw_command, nil}}}}
+    end
+  end
+
   test "can draw on an image using points" do
     Xairo.new_image("diagonal.png", 100, 100)
     |> Xairo.move_to(Point.new(10, 10))